@@ -3,6 +3,11 @@ use clap::{
     Subcommand,
 };
 
+use crate::oauth::{
+    DEVICE_URL,
+    TOKEN_URL,
+};
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
@@ -21,8 +26,30 @@ pub enum Commands {
         #[arg(short, long)]
         secret: String,
     },
+    /// Logs in and creates the credentials file
+    Login {
+        /// The client id
+        #[arg(short, long)]
+        id: String,
+
+        /// The client secret
+        #[arg(short, long)]
+        secret: String,
+
+        /// Log in with a code on another device, for machines without a browser
+        #[arg(long)]
+        device: bool,
+
+        /// The endpoint to request a device code from
+        #[arg(long, default_value = DEVICE_URL)]
+        device_url: String,
+
+        /// The endpoint to request the token from
+        #[arg(long, default_value = TOKEN_URL)]
+        token_url: String,
+    },
     Run {
-        /// The Twitch channel to run the bot on
-        channel_name: String,
+        /// The Twitch channels to run the bot on, in addition to the ones in the config
+        channel_names: Vec<String>,
     },
 }
@@ -13,6 +13,10 @@ use crate::{
         ArgumentLength,
         Command,
         CommandError,
+        config::{
+            CommandConfig,
+            CommandConfigOptions,
+        },
         placeholders::{
             self,
         },
@@ -89,7 +93,17 @@ impl Command for Average {
         ArgumentLength::Variadic
     }
 
-    async fn invoke(&self, state: State, author: &str, args: &[&str]) -> Result<(), CommandError> {
+    fn options<'a>(&self, config: &'a CommandConfig) -> &'a CommandConfigOptions {
+        &config.average_command
+    }
+
+    async fn invoke(
+        &self,
+        state: State,
+        channel: &str,
+        author: &str,
+        args: &[&str],
+    ) -> Result<(), CommandError> {
         let input = args.join(" ");
         let mut parts = input.splitn(2, "||");
         let queried_item = parts
@@ -181,7 +195,8 @@ impl Command for Average {
 
         state
             .send_command_response(
-                &state.config.command_config.average_command.format,
+                channel,
+                &self.options(state.settings().command_cfg(channel)).format,
                 author,
                 [
                     &placeholders::Average(average.to_string()) as &dyn Placeholder,
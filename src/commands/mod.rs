@@ -11,7 +11,13 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use twitch_irc::message::ServerMessage;
 
 use crate::{
-    commands::average::Average,
+    commands::{
+        average::Average,
+        config::{
+            CommandConfig,
+            CommandConfigOptions,
+        },
+    },
     state::State,
 };
 
@@ -43,7 +49,17 @@ pub trait Command: Send {
 
     fn length(&self) -> ArgumentLength;
 
-    async fn invoke(&self, state: State, author: &str, args: &[&str]) -> Result<(), CommandError>;
+    /// The command's options in the given config
+    fn options<'a>(&self, config: &'a CommandConfig) -> &'a CommandConfigOptions;
+
+    /// Runs the command. `channel` is the channel the command was sent in.
+    async fn invoke(
+        &self,
+        state: State,
+        channel: &str,
+        author: &str,
+        args: &[&str],
+    ) -> Result<(), CommandError>;
 
     fn check_length(&self, args: &[&str]) -> Option<String> {
         let arg_count = args.len();
@@ -70,15 +86,26 @@ pub async fn listen_to_commands(
     mut incoming_messages: UnboundedReceiver<ServerMessage>,
     state: State,
 ) -> anyhow::Result<()> {
-    let command_config = &state.config.command_config;
+    let mut settings = state.settings.clone();
+    let mut command_map = create_command_map(enabled_commands(&state));
+
+    while let Some(server_message) = incoming_messages.recv().await {
+        if settings.has_changed().unwrap_or(false) {
+            settings.borrow_and_update();
+            command_map = create_command_map(enabled_commands(&state));
+        }
 
-    let commands = commands![
-        command_config.average_command.enabled => Average,
-    ];
+        if let ServerMessage::UserState(msg) = &server_message {
+            let privileged = msg
+                .badges
+                .iter()
+                .any(|badge| matches!(badge.name.as_str(), "broadcaster" | "moderator" | "vip"));
 
-    let command_map = create_command_map(commands);
+            state
+                .messages
+                .set_privileged(&msg.channel_login, privileged);
+        }
 
-    while let Some(server_message) = incoming_messages.recv().await {
         if let ServerMessage::Privmsg(msg) = server_message {
             // there will sometimes be a `\u{e0000}` character at the end.
             // this character is NOT from twitch but rather add-ons (like 7tv)
@@ -93,17 +120,27 @@ pub async fn listen_to_commands(
                 continue;
             };
 
+            if !command
+                .options(state.settings().command_cfg(&msg.channel_login))
+                .enabled
+            {
+                continue;
+            }
+
             let args = &split[1..];
 
             if let Some(error_message) = command.check_length(args) {
                 state
-                    .client
-                    .say(state.channel_name.to_string(), error_message)
+                    .messages
+                    .send(msg.channel_login.clone(), error_message)
                     .await?;
                 continue;
             }
 
-            if let Err(err) = command.invoke(state.clone(), &msg.sender.name, args).await {
+            if let Err(err) = command
+                .invoke(state.clone(), &msg.channel_login, &msg.sender.name, args)
+                .await
+            {
                 match err {
                     CommandError::Server(error) => {
                         tracing::error!(?error);
@@ -111,8 +148,8 @@ pub async fn listen_to_commands(
                     }
                     CommandError::Client(message) => {
                         state
-                            .client
-                            .say(state.channel_name.to_string(), message)
+                            .messages
+                            .send(msg.channel_login.clone(), message)
                             .await?;
                     }
                 }
@@ -123,6 +160,13 @@ pub async fn listen_to_commands(
     Ok(())
 }
 
+/// Every command that is enabled in at least one channel
+fn enabled_commands(state: &State) -> Vec<Box<dyn Command>> {
+    commands![
+        state.command_enabled(|cfg| &cfg.average_command) => Average,
+    ]
+}
+
 fn create_command_map(commands: Vec<Box<dyn Command>>) -> HashMap<&'static str, Box<dyn Command>> {
     let mut map = HashMap::new();
 
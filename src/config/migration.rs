@@ -0,0 +1,378 @@
+use anyhow::{
+    Context,
+    bail,
+};
+use semver::Version;
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::config::Config;
+
+/// A single, versioned migration step.
+///
+/// Migrations run on the raw json before it is merged into the current defaults, so they can
+/// move values around that would otherwise be dropped (e.g. a renamed key).
+pub struct Migration {
+    /// The version this migration upgrades a config to.
+    pub version: Version,
+    pub migrate: fn(&mut Map<String, Value>, &mut MigrationReport),
+}
+
+/// All known migrations, in ascending order of their version.
+///
+/// Only add a step here if a key was renamed or its shape changed. Added and removed keys are
+/// handled by merging into the defaults.
+pub static MIGRATIONS: &[Migration] = &[Migration {
+    version: Version::new(0, 5, 0),
+    migrate: rename_s_tier_arbitrations,
+}];
+
+/// `s_tier_arbitrations` became `arbitrations`, with configurable tiers
+fn rename_s_tier_arbitrations(config: &mut Map<String, Value>, report: &mut MigrationReport) {
+    let channels = config
+        .get("channels")
+        .and_then(Value::as_object)
+        .map(|channels| channels.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    let listener_configs = std::iter::once(vec!["listener_config"]).chain(
+        channels
+            .iter()
+            .map(|channel| vec!["channels", channel, "listener_config"]),
+    );
+
+    for path in listener_configs {
+        rename(config, &path, "s_tier_arbitrations", "arbitrations", report);
+        rename(
+            config,
+            &path,
+            "s_tier_arbitrations_warning",
+            "arbitrations_warning",
+            report,
+        );
+    }
+}
+
+/// Renames the key `from` to `to` in the object at `path`, if both exist
+fn rename(
+    config: &mut Map<String, Value>,
+    path: &[&str],
+    from: &str,
+    to: &str,
+    report: &mut MigrationReport,
+) {
+    let Some(object) = path
+        .iter()
+        .try_fold(config, |object, key| object.get_mut(*key)?.as_object_mut())
+    else {
+        return;
+    };
+
+    if let Some(value) = object.remove(from) {
+        object.insert(to.to_owned(), value);
+
+        let path = path.join(".");
+        report
+            .renamed
+            .push((format!("{path}.{from}"), format!("{path}.{to}")));
+    }
+}
+
+/// Describes what happened to the keys of a config while migrating it.
+///
+/// Keys are dotted paths, like `listener_config.eidolon_hunts.format`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub added: Vec<String>,
+    pub renamed: Vec<(String, String)>,
+    pub dropped: Vec<String>,
+}
+
+impl MigrationReport {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.renamed.is_empty() && self.dropped.is_empty()
+    }
+
+    pub fn log(&self) {
+        for key in &self.added {
+            tracing::info!("Added `{key}` with its default value");
+        }
+
+        for (from, to) in &self.renamed {
+            tracing::info!("Renamed `{from}` to `{to}`");
+        }
+
+        for key in &self.dropped {
+            tracing::warn!("Dropped unknown key `{key}`");
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Migrated {
+    pub config: Config,
+    /// The version the config was written with. Configs without a (valid) version are treated
+    /// as `0.0.0`.
+    pub from: Version,
+    pub report: MigrationReport,
+}
+
+/// Migrates a raw config to the current version, keeping every user setting that still exists.
+pub fn migrate(raw: Value) -> anyhow::Result<Migrated> {
+    migrate_with(raw, MIGRATIONS)
+}
+
+fn migrate_with(raw: Value, migrations: &[Migration]) -> anyhow::Result<Migrated> {
+    let Value::Object(mut user) = raw else {
+        bail!("expected config.json to contain an object");
+    };
+
+    let defaults = Config::default();
+
+    let from = user
+        .get("version")
+        .and_then(Value::as_str)
+        .and_then(|version| version.parse::<Version>().ok())
+        .unwrap_or_else(|| {
+            tracing::warn!("config.json has no valid version, assuming it is outdated");
+            Version::new(0, 0, 0)
+        });
+
+    if from > defaults.version {
+        tracing::warn!(
+            "config.json was written by a newer version ({from}), some settings might be lost"
+        );
+    }
+
+    let mut report = MigrationReport::default();
+
+    for migration in migrations
+        .iter()
+        .filter(|migration| from < migration.version && migration.version <= defaults.version)
+    {
+        tracing::info!("Applying config migration to {}", migration.version);
+        (migration.migrate)(&mut user, &mut report);
+    }
+
+    user.insert(
+        "version".to_owned(),
+        Value::String(defaults.version.to_string()),
+    );
+
+    let merged = merge(
+        serde_json::to_value(&defaults)?,
+        Value::Object(user),
+        "",
+        &mut report,
+    );
+
+    let config = serde_json::from_value(merged).context("config.json contains invalid values")?;
+
+    Ok(Migrated {
+        config,
+        from,
+        report,
+    })
+}
+
+/// Merges `user` into `default`, key by key.
+///
+/// Keys missing from `user` are taken from `default`, keys unknown to `default` are dropped.
+/// Leaves and free-form maps (empty objects in the defaults) are taken from `user` as-is.
+fn merge(default: Value, user: Value, path: &str, report: &mut MigrationReport) -> Value {
+    match (default, user) {
+        (Value::Object(defaults), Value::Object(mut user)) if !defaults.is_empty() => {
+            let mut merged = Map::new();
+
+            for (key, default_value) in defaults {
+                let key_path = join_path(path, &key);
+
+                let value = match user.remove(&key) {
+                    Some(user_value) => merge(default_value, user_value, &key_path, report),
+                    None => {
+                        report.added.push(key_path);
+                        default_value
+                    }
+                };
+
+                merged.insert(key, value);
+            }
+
+            report
+                .dropped
+                .extend(user.keys().map(|key| join_path(path, key)));
+
+            Value::Object(merged)
+        }
+        (_, user) => user,
+    }
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+    use serde_json::{
+        Map,
+        Value,
+        json,
+    };
+
+    use crate::config::{
+        Config,
+        migration::{
+            Migration,
+            MigrationReport,
+            merge,
+            migrate,
+            migrate_with,
+        },
+    };
+
+    #[test]
+    fn test_merge_keeps_user_values() {
+        let mut report = MigrationReport::default();
+
+        let merged = merge(
+            json!({ "a": { "enabled": true, "format": "default" }, "b": 1 }),
+            json!({ "a": { "enabled": false }, "c": 2 }),
+            "",
+            &mut report,
+        );
+
+        assert_eq!(
+            merged,
+            json!({ "a": { "enabled": false, "format": "default" }, "b": 1 })
+        );
+        assert_eq!(report.added, ["a.format", "b"]);
+        assert_eq!(report.dropped, ["c"]);
+    }
+
+    #[test]
+    fn test_merge_keeps_free_form_maps() {
+        let mut report = MigrationReport::default();
+
+        let merged = merge(
+            json!({ "map": {} }),
+            json!({ "map": { "anything": { "goes": true } } }),
+            "",
+            &mut report,
+        );
+
+        assert_eq!(merged, json!({ "map": { "anything": { "goes": true } } }));
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_applies_pending_migrations() {
+        fn rename_hunts(config: &mut Map<String, Value>, report: &mut MigrationReport) {
+            let listener_config = config["listener_config"].as_object_mut().unwrap();
+
+            if let Some(hunts) = listener_config.remove("hunts") {
+                listener_config.insert("eidolon_hunts".to_owned(), hunts);
+                report.renamed.push((
+                    "listener_config.hunts".to_owned(),
+                    "listener_config.eidolon_hunts".to_owned(),
+                ));
+            }
+        }
+
+        fn already_applied(_: &mut Map<String, Value>, _: &mut MigrationReport) {
+            panic!("migration for an older version was applied");
+        }
+
+        let defaults = Config::default();
+
+        let migrations = [
+            Migration {
+                version: Version::new(0, 1, 0),
+                migrate: already_applied,
+            },
+            Migration {
+                version: defaults.version.clone(),
+                migrate: rename_hunts,
+            },
+        ];
+
+        let migrated = migrate_with(
+            json!({
+                "version": "0.1.0",
+                "listener_config": {
+                    "hunts": { "enabled": false, "format": "custom" }
+                }
+            }),
+            &migrations,
+        )
+        .unwrap();
+
+        assert_eq!(migrated.from, Version::new(0, 1, 0));
+        assert_eq!(migrated.config.version, defaults.version);
+        assert!(
+            !migrated
+                .config
+                .listener_config
+                .eidolon_hunts
+                .options
+                .enabled
+        );
+        assert_eq!(
+            migrated.config.listener_config.eidolon_hunts.options.format,
+            "custom"
+        );
+        assert_eq!(
+            migrated.config.listener_config.meta_relics,
+            defaults.listener_config.meta_relics
+        );
+        assert_eq!(
+            migrated.report.renamed,
+            [(
+                "listener_config.hunts".to_owned(),
+                "listener_config.eidolon_hunts".to_owned()
+            )]
+        );
+        assert!(migrated.report.dropped.is_empty());
+    }
+
+    #[test]
+    fn test_migrate_renames_s_tier_arbitrations() {
+        let migrated = migrate(json!({
+            "version": "0.4.1",
+            "channels": {
+                "some_channel": {
+                    "listener_config": {
+                        "s_tier_arbitrations": { "enabled": false }
+                    }
+                }
+            },
+            "listener_config": {
+                "s_tier_arbitrations": { "enabled": true, "format": "custom" }
+            }
+        }))
+        .unwrap();
+
+        let arbitrations = &migrated.config.listener_config.arbitrations;
+
+        assert_eq!(arbitrations.options.options.format, "custom");
+        assert_eq!(
+            arbitrations.tiers,
+            Config::default().listener_config.arbitrations.tiers
+        );
+        assert_eq!(
+            migrated.config.channels["some_channel"].listener_config["arbitrations"],
+            json!({ "enabled": false })
+        );
+        assert!(migrated.report.renamed.contains(&(
+            "listener_config.s_tier_arbitrations".to_owned(),
+            "listener_config.arbitrations".to_owned()
+        )));
+    }
+}
@@ -0,0 +1,308 @@
+pub mod migration;
+pub mod reload;
+
+use std::{
+    collections::{
+        BTreeMap,
+        HashMap,
+    },
+    fs,
+    str::FromStr,
+};
+
+use anyhow::Context;
+use better_default::Default;
+use semver::Version;
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+use crate::{
+    commands::config::CommandConfig,
+    config::migration::Migrated,
+    listener::config::ListenerConfig,
+    placeholder::ChannelName,
+};
+
+pub const CONFIG_PATH: &str = "./config.json";
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct Config {
+    #[default(Version::from_str(env!("CARGO_PKG_VERSION")).unwrap())]
+    pub version: Version,
+    /// The channels to join, keyed by their name
+    pub channels: BTreeMap<String, ChannelConfig>,
+    pub listener_config: ListenerConfig,
+    pub command_config: CommandConfig,
+}
+
+/// Settings for a single channel the bot joins
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct ChannelConfig {
+    /// Overrides for the global `listener_config`, e.g. `{ "eidolon_hunts": { "enabled": false }
+    /// }`
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub listener_config: Map<String, Value>,
+
+    /// Overrides for the global `command_config`
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub command_config: Map<String, Value>,
+}
+
+/// The config of a single channel, with its overrides applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedChannelConfig {
+    pub listener_config: ListenerConfig,
+    pub command_config: CommandConfig,
+}
+
+/// A config together with the resolved config of every joined channel.
+///
+/// This is what gets swapped out when config.json is reloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub config: Config,
+    pub channels: HashMap<ChannelName, ResolvedChannelConfig>,
+}
+
+impl Settings {
+    pub fn new(config: Config, channels: &[ChannelName]) -> anyhow::Result<Self> {
+        let channels = channels
+            .iter()
+            .map(|channel| Ok((channel.clone(), config.resolve(&channel.0)?)))
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self { config, channels })
+    }
+
+    pub fn listener_cfg(&self, channel: &str) -> &ListenerConfig {
+        self.channels
+            .get(channel)
+            .map_or(&self.config.listener_config, |config| {
+                &config.listener_config
+            })
+    }
+
+    pub fn command_cfg(&self, channel: &str) -> &CommandConfig {
+        self.channels
+            .get(channel)
+            .map_or(&self.config.command_config, |config| &config.command_config)
+    }
+}
+
+impl Config {
+    /// Applies the overrides of `channel` on top of the global config. Channels without an entry
+    /// in `channels` use the global config.
+    pub fn resolve(&self, channel: &str) -> anyhow::Result<ResolvedChannelConfig> {
+        let resolved = match self
+            .channels
+            .iter()
+            .find_map(|(name, config)| name.eq_ignore_ascii_case(channel).then_some(config))
+        {
+            Some(channel_config) => ResolvedChannelConfig {
+                listener_config: with_overrides(
+                    &self.listener_config,
+                    &channel_config.listener_config,
+                )
+                .context(format!("invalid listener_config override for {channel}"))?,
+                command_config: with_overrides(
+                    &self.command_config,
+                    &channel_config.command_config,
+                )
+                .context(format!("invalid command_config override for {channel}"))?,
+            },
+            None => ResolvedChannelConfig {
+                listener_config: self.listener_config.clone(),
+                command_config: self.command_config.clone(),
+            },
+        };
+
+        Ok(resolved)
+    }
+
+    fn load_or_create() -> anyhow::Result<Self> {
+        match fs::read_to_string(CONFIG_PATH) {
+            Ok(contents) => {
+                let raw = serde_json::from_str(&contents).context("malformed config.json")?;
+
+                let Migrated {
+                    config,
+                    from,
+                    report,
+                } = migration::migrate(raw)?;
+
+                if from != config.version || !report.is_empty() {
+                    tracing::info!(
+                        "Updating config.json (found version {}, expected {})",
+                        from,
+                        config.version
+                    );
+
+                    let backup_path = format!("{CONFIG_PATH}.{from}.bak");
+                    fs::write(&backup_path, contents)
+                        .context(format!("Failed to back up config to {backup_path}"))?;
+                    tracing::info!("Backed up the old config to {backup_path}");
+
+                    report.log();
+
+                    fs::write(CONFIG_PATH, serde_json::to_string_pretty(&config)?)?;
+                }
+
+                Ok(config)
+            }
+            Err(_) => {
+                let config = Config::default();
+                fs::write(CONFIG_PATH, serde_json::to_string_pretty(&config)?)?;
+
+                Ok(config)
+            }
+        }
+    }
+
+    pub fn load() -> anyhow::Result<Self> {
+        Self::load_or_create()?.validated()
+    }
+
+    /// Reads and migrates config.json without writing anything back
+    pub fn read() -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(CONFIG_PATH)
+            .context(format!("Failed to read config at {CONFIG_PATH}"))?;
+        let raw = serde_json::from_str(&contents).context("malformed config.json")?;
+
+        migration::migrate(raw)?.config.validated()
+    }
+
+    fn validated(self) -> anyhow::Result<Self> {
+        for channel in self.channels.keys() {
+            self.resolve(channel)?;
+        }
+
+        Ok(self)
+    }
+}
+
+fn with_overrides<T>(base: &T, overrides: &Map<String, Value>) -> anyhow::Result<T>
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let mut value = serde_json::to_value(base)?;
+
+    if let Value::Object(target) = &mut value {
+        apply_overrides(target, overrides);
+    }
+
+    let resolved = serde_json::from_value(value)?;
+
+    // unknown keys are ignored when deserializing, so a typo would silently do nothing
+    if let Value::Object(resolved_value) = serde_json::to_value(&resolved)? {
+        ensure_known_keys(&resolved_value, overrides, "")?;
+    }
+
+    Ok(resolved)
+}
+
+fn apply_overrides(target: &mut Map<String, Value>, overrides: &Map<String, Value>) {
+    for (key, value) in overrides {
+        match (target.get_mut(key), value) {
+            (Some(Value::Object(target)), Value::Object(overrides)) => {
+                apply_overrides(target, overrides)
+            }
+            _ => {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Fails if `overrides` has a key that didn't end up in `resolved`
+fn ensure_known_keys(
+    resolved: &Map<String, Value>,
+    overrides: &Map<String, Value>,
+    path: &str,
+) -> anyhow::Result<()> {
+    for (key, value) in overrides {
+        let path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+
+        match (resolved.get(key), value) {
+            (Some(Value::Object(resolved)), Value::Object(overrides)) => {
+                ensure_known_keys(resolved, overrides, &path)?
+            }
+            // unset optional values aren't serialized
+            (Some(_), _) | (None, Value::Null) => (),
+            (None, _) => anyhow::bail!("unknown key `{path}`"),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::config::{
+        ChannelConfig,
+        Config,
+    };
+
+    #[test]
+    fn test_resolve_applies_overrides() {
+        let mut config = Config::default();
+
+        config.channels.insert(
+            "overridden".to_owned(),
+            serde_json::from_value::<ChannelConfig>(json!({
+                "listener_config": {
+                    "eidolon_hunts": { "enabled": false },
+                    "meta_relics": { "format": "custom" }
+                }
+            }))
+            .unwrap(),
+        );
+
+        let resolved = config.resolve("overridden").unwrap();
+
+        assert!(!resolved.listener_config.eidolon_hunts.options.enabled);
+        assert_eq!(
+            resolved.listener_config.eidolon_hunts.options.format,
+            config.listener_config.eidolon_hunts.options.format
+        );
+        assert_eq!(
+            resolved.listener_config.meta_relics.options.format,
+            "custom"
+        );
+        assert_eq!(resolved.command_config, config.command_config);
+
+        let not_overridden = config.resolve("not_overridden").unwrap();
+
+        assert_eq!(not_overridden.listener_config, config.listener_config);
+    }
+
+    #[test]
+    fn test_resolve_rejects_unknown_keys() {
+        let mut config = Config::default();
+
+        config.channels.insert(
+            "typo".to_owned(),
+            serde_json::from_value::<ChannelConfig>(json!({
+                "listener_config": {
+                    "eidolon_hunts": { "enabeld": false }
+                }
+            }))
+            .unwrap(),
+        );
+
+        let error = config.resolve("typo").unwrap_err();
+
+        assert!(format!("{error:#}").contains("unknown key `eidolon_hunts.enabeld`"));
+    }
+}
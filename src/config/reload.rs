@@ -0,0 +1,65 @@
+use std::{
+    sync::Arc,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
+use tokio::{
+    fs,
+    sync::watch,
+};
+
+use crate::{
+    config::{
+        CONFIG_PATH,
+        Config,
+        Settings,
+    },
+    placeholder::ChannelName,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Watches config.json for changes and publishes every valid new version to `sender`.
+///
+/// Invalid configs are logged and ignored, so the last valid config stays active.
+pub async fn watch_config(
+    sender: watch::Sender<Arc<Settings>>,
+    channels: Arc<[ChannelName]>,
+) -> anyhow::Result<()> {
+    let mut last_modified = modified().await?;
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let modified = match modified().await {
+            Ok(modified) => modified,
+            Err(error) => {
+                tracing::warn!(?error, "Failed to check config.json for changes");
+                continue;
+            }
+        };
+
+        if modified == last_modified {
+            continue;
+        }
+
+        last_modified = modified;
+
+        match Config::read().and_then(|config| Settings::new(config, &channels)) {
+            Ok(settings) if settings == **sender.borrow() => (),
+            Ok(settings) => {
+                tracing::info!("Reloaded config.json");
+                sender.send_replace(Arc::new(settings));
+            }
+            Err(error) => tracing::error!("Not applying the changed config.json: {error:#}"),
+        }
+    }
+}
+
+async fn modified() -> anyhow::Result<SystemTime> {
+    Ok(fs::metadata(CONFIG_PATH).await?.modified()?)
+}
@@ -0,0 +1,175 @@
+use std::collections::BTreeSet;
+
+use chrono::{
+    TimeDelta,
+    Utc,
+};
+use tokio::task::JoinSet;
+
+use crate::{
+    listener::{
+        Listener,
+        config::{
+            ArbitrationTier,
+            ListenerConfig,
+        },
+        placeholders,
+        sleep_unless_reloaded,
+        warnings::send_warnings,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+/// How long an arbitration lasts
+const ARBITRATION_DURATION: TimeDelta = TimeDelta::hours(1);
+
+/// The tiers of every channel `enabled` returns true for
+fn subscribed_tiers(
+    state: &State,
+    enabled: impl Fn(&ListenerConfig) -> bool,
+) -> BTreeSet<ArbitrationTier> {
+    let settings = state.settings();
+
+    state
+        .channels
+        .iter()
+        .map(|channel| settings.listener_cfg(&channel.0))
+        .filter(|cfg| enabled(cfg))
+        .flat_map(|cfg| cfg.arbitrations.tiers.iter().copied())
+        .collect()
+}
+
+pub struct ArbitrationListener;
+
+impl Listener for ArbitrationListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut settings = state.settings.clone();
+        // pending end messages, aborted with the listener when it's stopped
+        let mut ends = JoinSet::new();
+
+        loop {
+            settings.borrow_and_update();
+            while ends.try_join_next().is_some() {}
+
+            let tiers = subscribed_tiers(&state, |cfg| cfg.arbitrations.options.options.enabled);
+
+            let Some((tier, next_arbi)) = tiers
+                .into_iter()
+                .filter_map(|tier| {
+                    let next_arbi = state.arbi_data.upcoming_by_tier(tier.into()).ok()?;
+                    Some((tier, next_arbi))
+                })
+                .min_by_key(|(_, next_arbi)| next_arbi.activation)
+            else {
+                return Ok(());
+            };
+
+            if next_arbi.activation > Utc::now() {
+                let time_to_sleep = (next_arbi.activation - Utc::now()).to_std()?;
+                tracing::info!(?time_to_sleep, upcoming_arbi = ?next_arbi);
+
+                // the subscribed tiers might have changed, look for the next arbitration again
+                if !sleep_unless_reloaded(&mut settings, time_to_sleep).await {
+                    continue;
+                }
+            }
+
+            let activation = next_arbi.activation.timestamp();
+            let node = placeholders::Node(&next_arbi.node);
+            let planet = placeholders::Planet(&next_arbi.planet);
+            let tier_placeholder = placeholders::Tier(tier.as_str());
+
+            state
+                .send_listener_message(&format!("arbitrations:{activation}"), |cfg| {
+                    let config = &cfg.arbitrations;
+
+                    (config.options.options.enabled && config.tiers.contains(&tier)).then(|| {
+                        apply_placeholders(
+                            config.format_for(tier),
+                            [&node as &dyn Placeholder, &planet, &tier_placeholder],
+                        )
+                    })
+                })
+                .await;
+
+            // the next arbitration might start right after this one, so don't wait for the end here
+            let ends_at = next_arbi.activation + ARBITRATION_DURATION;
+            let node = next_arbi.node.clone();
+            let planet = next_arbi.planet.clone();
+            let state = state.clone();
+
+            ends.spawn(async move {
+                if let Ok(until_end) = (ends_at - Utc::now()).to_std() {
+                    tokio::time::sleep(until_end).await;
+                }
+
+                let node = placeholders::Node(&node);
+                let planet = placeholders::Planet(&planet);
+                let tier_placeholder = placeholders::Tier(tier.as_str());
+
+                state
+                    .send_listener_end(
+                        &format!("arbitrations_end:{activation}"),
+                        |cfg| {
+                            let config = &cfg.arbitrations;
+                            config.tiers.contains(&tier).then_some(&config.options)
+                        },
+                        [&node as &dyn Placeholder, &planet, &tier_placeholder],
+                    )
+                    .await;
+            });
+        }
+    }
+}
+
+pub struct ArbitrationWarningListener;
+
+impl Listener for ArbitrationWarningListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut settings = state.settings.clone();
+
+        loop {
+            settings.borrow_and_update();
+
+            let tiers = subscribed_tiers(&state, |cfg| cfg.arbitrations_warning.options.enabled);
+
+            let Some((tier, next_arbi)) = tiers
+                .into_iter()
+                .filter_map(|tier| {
+                    let next_arbi = state.arbi_data.upcoming_by_tier(tier.into()).ok()?;
+                    Some((tier, next_arbi))
+                })
+                .min_by_key(|(_, next_arbi)| next_arbi.activation)
+            else {
+                return Ok(());
+            };
+
+            send_warnings(
+                &state,
+                &format!("arbitrations_warning:{}", next_arbi.activation.timestamp()),
+                next_arbi.activation,
+                |cfg| {
+                    cfg.arbitrations
+                        .tiers
+                        .contains(&tier)
+                        .then_some(&cfg.arbitrations_warning)
+                },
+                &[
+                    &placeholders::Node(&next_arbi.node),
+                    &placeholders::Planet(&next_arbi.planet),
+                    &placeholders::Tier(tier.as_str()),
+                ],
+            )
+            .await;
+
+            if next_arbi.activation > Utc::now() {
+                sleep_unless_reloaded(&mut settings, (next_arbi.activation - Utc::now()).to_std()?)
+                    .await;
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Error;
+use warframe::worldstate::queryable::ArchonHunt;
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders::{
+            Stage,
+            StagedMission,
+        },
+    },
+    state::State,
+};
+
+pub struct ArchonHuntListener;
+
+impl Listener for ArchonHuntListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_update_with_state::<_, ArchonHunt, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, before: &ArchonHunt, archon_hunt: &ArchonHunt) {
+    if before.id == archon_hunt.id {
+        return;
+    }
+
+    let stages = archon_hunt
+        .missions
+        .iter()
+        .map(|mission| Stage {
+            mission_type: &mission.r#type,
+            node: &mission.node,
+            modifier: None,
+        })
+        .collect::<Vec<_>>();
+
+    let mission = StagedMission::new(&archon_hunt.boss, &archon_hunt.faction, &stages);
+
+    state
+        .send_listener_response(
+            &format!("archon_hunts:{}", archon_hunt.id),
+            |cfg| &cfg.archon_hunts,
+            mission.placeholders(),
+        )
+        .await;
+}
@@ -0,0 +1,114 @@
+use anyhow::Error;
+use warframe::worldstate::{
+    Change,
+    SyndicateMission,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        config::BountyConfig,
+        placeholders,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+fn contains_any(haystack: &str, needles: &[String]) -> bool {
+    let haystack = haystack.to_lowercase();
+
+    needles
+        .iter()
+        .any(|needle| haystack.contains(&needle.to_lowercase()))
+}
+
+impl BountyConfig {
+    pub fn watches_syndicate(&self, syndicate: &str) -> bool {
+        contains_any(syndicate, &self.syndicates)
+    }
+
+    /// Whether a bounty is special or rewards a watched item
+    pub fn watches_bounty(&self, bounty: &str, reward_pool: &[String]) -> bool {
+        contains_any(bounty, &self.special_bounties)
+            || reward_pool
+                .iter()
+                .any(|reward| contains_any(reward, &self.reward_watchlist))
+    }
+}
+
+pub struct BountyListener;
+
+impl Listener for BountyListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, SyndicateMission, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, mission: &SyndicateMission, change: Change) {
+    if change != Change::Added {
+        return;
+    }
+
+    let syndicate = placeholders::Syndicate(&mission.syndicate);
+
+    for job in &mission.jobs {
+        let bounty_name = job.job_type.as_str();
+
+        let level_range = match (job.enemy_levels.first(), job.enemy_levels.last()) {
+            (Some(min), Some(max)) => format!("{min}-{max}"),
+            _ => "?".to_owned(),
+        };
+
+        let bounty = placeholders::Bounty(bounty_name);
+        let level_range = placeholders::LevelRange(level_range);
+        let rewards = placeholders::Rewards(job.reward_pool.join(", "));
+
+        state
+            .send_listener_message(&format!("bounties:{}", job.id), |cfg| {
+                let config = &cfg.bounties;
+
+                (config.options.enabled
+                    && config.watches_syndicate(&mission.syndicate)
+                    && config.watches_bounty(bounty_name, &job.reward_pool))
+                .then(|| {
+                    apply_placeholders(
+                        &config.options.format,
+                        [
+                            &syndicate as &dyn Placeholder,
+                            &bounty,
+                            &level_range,
+                            &rewards,
+                        ],
+                    )
+                })
+            })
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::listener::config::BountyConfig;
+
+    #[test]
+    fn test_watches_bounty() {
+        let config = BountyConfig {
+            reward_watchlist: vec!["Vome Residue".to_owned()],
+            ..Default::default()
+        };
+
+        assert!(config.watches_syndicate("The Holdfasts"));
+        assert!(!config.watches_syndicate("Nightwave"));
+
+        assert!(config.watches_bounty("Isolation Vault Bounty", &[]));
+        assert!(config.watches_bounty("Capture", &["1000X Vome Residue".to_owned()]));
+        assert!(!config.watches_bounty("Capture", &["Credit Cache".to_owned()]));
+    }
+}
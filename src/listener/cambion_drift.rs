@@ -0,0 +1,49 @@
+use anyhow::Error;
+use warframe::worldstate::{
+    CambionDriftState,
+    TimedEvent,
+    queryable::CambionDrift,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+    },
+    placeholder::Placeholder,
+    state::State,
+};
+
+pub struct CambionDriftListener;
+
+impl Listener for CambionDriftListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_update_with_state::<_, CambionDrift, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, before: &CambionDrift, cambion_drift: &CambionDrift) {
+    if before.state == cambion_drift.state {
+        return;
+    }
+
+    let cycle_state = match cambion_drift.state {
+        CambionDriftState::Fass => "Fass",
+        CambionDriftState::Vome => "Vome",
+    };
+
+    state
+        .send_listener_response(
+            &format!("cambion_drift:{}", cambion_drift.expiry().timestamp()),
+            |cfg| &cfg.cambion_drift,
+            [
+                &placeholders::CycleState(cycle_state) as &dyn Placeholder,
+                &placeholders::TimeLeft::until(cambion_drift.expiry()),
+            ],
+        )
+        .await;
+}
@@ -1,4 +1,11 @@
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
 use better_default::Default;
+use chrono::NaiveTime;
+use chrono_tz::Tz;
 use serde::{
     Deserialize,
     Serialize,
@@ -25,21 +32,541 @@ impl From<&str> for ListenerConfigOptions {
     }
 }
 
+impl AsRef<ListenerConfigOptions> for ListenerConfigOptions {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        self
+    }
+}
+
+/// The options of a listener whose event has an end
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct EndingListenerConfig {
+    #[serde(flatten)]
+    pub options: ListenerConfigOptions,
+
+    /// Sent when the event ends, if set
+    #[serde(default)]
+    pub end_format: Option<String>,
+}
+
+impl From<&str> for EndingListenerConfig {
+    fn from(value: &str) -> Self {
+        Self {
+            options: value.into(),
+            end_format: None,
+        }
+    }
+}
+
+impl AsRef<ListenerConfigOptions> for EndingListenerConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct VoidTraderConfig {
+    #[serde(flatten)]
+    #[default("🛒 @{channel_name}, Baro Ki'Teer arrived at {relay}! He leaves in {departure}. Notable items: {inventory}".into())]
+    pub options: ListenerConfigOptions,
+
+    /// Items to list in `{inventory}`, matched case-insensitively by name. `Primed` matches
+    /// every primed mod, for example. If empty, every item is listed.
+    #[default(vec!["Primed".to_owned()])]
+    pub notable_items: Vec<String>,
+}
+
+impl AsRef<ListenerConfigOptions> for VoidTraderConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+/// An item to watch for, e.g. in invasion rewards
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WatchedItem {
+    /// Matched case-insensitively against the item's name. `Wraith` matches every Wraith part,
+    /// for example.
+    pub item: String,
+
+    /// The minimum amount of the item
+    #[serde(default = "WatchedItem::default_min_count")]
+    pub min_count: u32,
+}
+
+impl WatchedItem {
+    pub fn new(item: impl Into<String>) -> Self {
+        Self {
+            item: item.into(),
+            min_count: Self::default_min_count(),
+        }
+    }
+
+    fn default_min_count() -> u32 {
+        1
+    }
+
+    pub fn matches(&self, name: &str, count: u32) -> bool {
+        count >= self.min_count && name.to_lowercase().contains(&self.item.to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct InvasionConfig {
+    #[serde(flatten)]
+    #[default("⚔️ @{channel_name}, invasion on {node} ({attacker} vs. {defender}) rewards {reward}!".into())]
+    pub options: ListenerConfigOptions,
+
+    /// Only invasions rewarding one of these items are sent
+    #[default(vec![
+        WatchedItem::new("Orokin Catalyst"),
+        WatchedItem::new("Orokin Reactor"),
+        WatchedItem::new("Forma"),
+        WatchedItem::new("Wraith"),
+        WatchedItem::new("Vandal"),
+    ])]
+    pub watchlist: Vec<WatchedItem>,
+}
+
+impl AsRef<ListenerConfigOptions> for InvasionConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct NightwaveConfig {
+    #[serde(flatten)]
+    #[default("🌃 @{channel_name}, new {kind} Nightwave challenge: {title} - {description} ({standing} Standing)".into())]
+    pub options: ListenerConfigOptions,
+
+    /// Only send Elite Weekly challenges
+    pub only_elite_weeklies: bool,
+}
+
+impl AsRef<ListenerConfigOptions> for NightwaveConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct DuviriCircuitConfig {
+    #[serde(flatten)]
+    #[default("🌀 @{channel_name}, new Circuit rotation! Warframes: {warframes} | Incarnons: {incarnons}".into())]
+    pub options: ListenerConfigOptions,
+
+    /// Only send the rotation if one of these Steel Path incarnon adapters is available, e.g.
+    /// `Boltor`. If empty, every rotation is sent.
+    pub incarnon_watchlist: Vec<String>,
+}
+
+impl AsRef<ListenerConfigOptions> for DuviriCircuitConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+/// Darvo's daily deal or a market flash sale
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DealConfig {
+    #[serde(flatten)]
+    pub options: ListenerConfigOptions,
+
+    /// Deals with a smaller discount (in percent) are not sent
+    pub min_discount: u32,
+}
+
+impl DealConfig {
+    pub fn new(s: impl Into<String>, min_discount: u32) -> Self {
+        Self {
+            options: ListenerConfigOptions::new(s),
+            min_discount,
+        }
+    }
+}
+
+impl AsRef<ListenerConfigOptions> for DealConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+/// The kind of a news post
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum NewsTag {
+    Update,
+    PrimeAccess,
+    Stream,
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct NewsConfig {
+    #[serde(flatten)]
+    #[default("📰 @{channel_name}, {title} {link}".into())]
+    pub options: ListenerConfigOptions,
+
+    /// The kinds of news to send. If empty, every post is sent.
+    #[default(vec![NewsTag::Update, NewsTag::PrimeAccess])]
+    pub tags: Vec<NewsTag>,
+}
+
+impl AsRef<ListenerConfigOptions> for NewsConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+/// Open-world bounties. Names are matched case-insensitively and partially, so `Holdfasts`
+/// matches `The Holdfasts`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct BountyConfig {
+    #[serde(flatten)]
+    #[default("🎯 @{channel_name}, {syndicate} have a {bounty} (level {level_range}) with {rewards}!".into())]
+    pub options: ListenerConfigOptions,
+
+    /// The syndicates whose bounties to check
+    #[default(vec![
+        "Ostrons".to_owned(),
+        "Solaris United".to_owned(),
+        "Entrati".to_owned(),
+        "Holdfasts".to_owned(),
+    ])]
+    pub syndicates: Vec<String>,
+
+    /// Bounties rewarding one of these items are sent
+    pub reward_watchlist: Vec<String>,
+
+    /// Bounties of these kinds are always sent
+    #[default(vec!["Isolation Vault".to_owned(), "Narmer".to_owned()])]
+    pub special_bounties: Vec<String>,
+}
+
+impl AsRef<ListenerConfigOptions> for BountyConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
+/// A tier of the "Arbitration Goons" tierlist
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArbitrationTier {
+    S,
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl ArbitrationTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArbitrationTier::S => "S",
+            ArbitrationTier::A => "A",
+            ArbitrationTier::B => "B",
+            ArbitrationTier::C => "C",
+            ArbitrationTier::D => "D",
+            ArbitrationTier::F => "F",
+        }
+    }
+}
+
+impl From<ArbitrationTier> for arbitration_data::Tier {
+    fn from(value: ArbitrationTier) -> Self {
+        match value {
+            ArbitrationTier::S => arbitration_data::Tier::S,
+            ArbitrationTier::A => arbitration_data::Tier::A,
+            ArbitrationTier::B => arbitration_data::Tier::B,
+            ArbitrationTier::C => arbitration_data::Tier::C,
+            ArbitrationTier::D => arbitration_data::Tier::D,
+            ArbitrationTier::F => arbitration_data::Tier::F,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct ArbitrationConfig {
+    #[serde(flatten)]
+    #[default("💰 @{channel_name}, new {tier}-Tier Arbitration: {node} on {planet}".into())]
+    pub options: EndingListenerConfig,
+
+    /// The tiers to notify about
+    #[default(BTreeSet::from([ArbitrationTier::S]))]
+    pub tiers: BTreeSet<ArbitrationTier>,
+
+    /// Formats for specific tiers, used instead of `format`
+    pub tier_formats: BTreeMap<ArbitrationTier, String>,
+}
+
+impl ArbitrationConfig {
+    pub fn format_for(&self, tier: ArbitrationTier) -> &str {
+        self.tier_formats
+            .get(&tier)
+            .unwrap_or(&self.options.options.format)
+    }
+}
+
+impl AsRef<ListenerConfigOptions> for ArbitrationConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        self.options.as_ref()
+    }
+}
+
+/// A relic tier, as found on fissures
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub enum RelicTier {
+    Lith,
+    Meso,
+    Neo,
+    Axi,
+    Requiem,
+    Omnia,
+}
+
+impl RelicTier {
+    pub fn matches(&self, tier: &warframe::worldstate::Tier) -> bool {
+        use warframe::worldstate::Tier;
+
+        match self {
+            RelicTier::Lith => *tier == Tier::Lith,
+            RelicTier::Meso => *tier == Tier::Meso,
+            RelicTier::Neo => *tier == Tier::Neo,
+            RelicTier::Axi => *tier == Tier::Axi,
+            RelicTier::Requiem => *tier == Tier::Requiem,
+            RelicTier::Omnia => *tier == Tier::Omnia,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct VoidStormConfig {
+    #[serde(flatten)]
+    #[default("🌪️ @{channel_name}, new {tier} Void Storm on {node}! It expires in {expiry}".into())]
+    pub options: EndingListenerConfig,
+
+    /// The relic tiers to notify about. If empty, every tier is sent.
+    pub relic_tiers: Vec<RelicTier>,
+}
+
+impl VoidStormConfig {
+    pub fn watches(&self, tier: &warframe::worldstate::Tier) -> bool {
+        self.relic_tiers.is_empty() || self.relic_tiers.iter().any(|watched| watched.matches(tier))
+    }
+}
+
+impl AsRef<ListenerConfigOptions> for VoidStormConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        self.options.as_ref()
+    }
+}
+
+/// A user-defined fissure alert. A fissure matches if it matches every filter that is set.
+///
+/// List filters match if the fissure matches any of their entries. Names are matched
+/// case-insensitively.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct FissureRule {
+    #[serde(flatten)]
+    pub options: ListenerConfigOptions,
+
+    /// e.g. `Defense` or `Mobile Defense`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mission_types: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relic_tiers: Vec<RelicTier>,
+
+    /// Steel Path fissures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_hard: Option<bool>,
+
+    /// Void Storms (Railjack fissures)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_storm: Option<bool>,
+
+    /// Node names without the planet, e.g. `Hydron`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub planets: Vec<String>,
+
+    /// e.g. `Grineer`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub factions: Vec<String>,
+
+    /// Tiers of the node on the "Arbitration Goons" tierlist
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub map_tiers: Vec<ArbitrationTier>,
+}
+
+/// A daily time window, e.g. from `22:00` to `08:00`
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// When listener notifications may be sent
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub struct ScheduleConfig {
+    /// The IANA time zone `quiet_hours` are in, e.g. `Europe/Berlin`. Follows daylight saving
+    /// time.
+    #[default(Tz::UTC)]
+    pub time_zone: Tz,
+
+    /// Notifications aren't sent during these windows
+    pub quiet_hours: Vec<QuietHours>,
+
+    /// Only send notifications while the channel is live
+    pub only_while_live: bool,
+
+    /// Send a summary of the notifications that weren't sent once sending is allowed again.
+    /// Otherwise, they are dropped.
+    pub summarize_suppressed: bool,
+
+    #[default("📋 @{channel_name}, you missed {count} notifications: {notifications}".into())]
+    pub summary_format: String,
+}
+
+/// A heads-up some time before a scheduled event starts
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct WarningConfig {
+    #[serde(flatten)]
+    pub options: ListenerConfigOptions,
+
+    /// How many minutes before the event starts the warning is sent
+    pub minutes_before: u32,
+}
+
+impl WarningConfig {
+    pub fn new(s: impl Into<String>, minutes_before: u32) -> Self {
+        Self {
+            options: ListenerConfigOptions::new(s),
+            minutes_before,
+        }
+    }
+}
+
+impl AsRef<ListenerConfigOptions> for WarningConfig {
+    fn as_ref(&self) -> &ListenerConfigOptions {
+        &self.options
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Default)]
 pub struct ListenerConfig {
     /// Eidolon Hunts
     #[default("🌙 @{channel_name}, swing yo' ass over to Cetus! It's EIDOLON TIME!".into())]
-    pub eidolon_hunts: ListenerConfigOptions,
+    pub eidolon_hunts: EndingListenerConfig,
+
+    /// A heads-up before Eidolon Hunts
+    #[default(WarningConfig::new("⏰ @{channel_name}, eidolon night starts in {time_left}!", 5))]
+    pub eidolon_hunts_warning: WarningConfig,
+
+    /// Orb Vallis turning warm
+    #[default("☀️ @{channel_name}, Orb Vallis is warm for {time_left}!".into())]
+    pub orb_vallis_warm: EndingListenerConfig,
 
-    /// S Tier Arbitrations, based on the "Arbitration Goons" Tierlist
-    #[default("💰 @{channel_name}, new S-Tier Arbitration: {node} on {planet}".into())]
-    pub s_tier_arbitrations: ListenerConfigOptions,
+    /// Cambion Drift switching between Fass and Vome
+    #[default("🪱 @{channel_name}, it's {state} on the Cambion Drift for {time_left}!".into())]
+    pub cambion_drift: ListenerConfigOptions,
+
+    /// The Zariman switching between the Corpus and the Grineer
+    #[default("🚀 @{channel_name}, the {faction} took over the Zariman for {time_left}!".into())]
+    pub zariman: ListenerConfigOptions,
+
+    /// Arbitrations of the configured tiers, based on the "Arbitration Goons" Tierlist
+    pub arbitrations: ArbitrationConfig,
+
+    /// A heads-up before Arbitrations of the tiers configured in `arbitrations`
+    #[default(WarningConfig::new(
+        "⏰ @{channel_name}, {tier}-Tier Arbitration on {node} ({planet}) starts in {time_left}!",
+        5
+    ))]
+    pub arbitrations_warning: WarningConfig,
 
     /// Meta Relics. These are just S-Tier Arbitration maps, but fissures
     #[default("🔍 @{channel_name} New Meta Fissure detected on {node} - {difficulty}".into())]
-    pub meta_relics: ListenerConfigOptions,
+    pub meta_relics: EndingListenerConfig,
 
     /// What the name implies, Steel Path Disruption Fissures
     #[default("⚡ @{channel_name} New Steel Path Disruption Fissure detected on {node}".into())]
-    pub steel_path_disruption_fissures: ListenerConfigOptions,
+    pub steel_path_disruption_fissures: EndingListenerConfig,
+
+    /// Void Storms, which are Railjack fissures
+    pub void_storms: VoidStormConfig,
+
+    /// User-defined fissure alerts. Only the first matching rule is sent.
+    pub fissure_rules: Vec<FissureRule>,
+
+    /// Invasions rewarding watched items
+    pub invasions: InvasionConfig,
+
+    /// Open-world bounties with watched rewards, or special ones
+    pub bounties: BountyConfig,
+
+    /// Acolytes and other persistent enemies being discovered
+    #[default("👹 @{channel_name}, {enemy} was spotted on {node} with {health}% health left!".into())]
+    pub persistent_enemies: ListenerConfigOptions,
+
+    /// Acolytes and other persistent enemies being defeated
+    #[default("💀 @{channel_name}, {enemy} was defeated!".into())]
+    pub persistent_enemies_defeated: ListenerConfigOptions,
+
+    /// Baro Ki'Teer arriving at a relay
+    pub void_trader: VoidTraderConfig,
+
+    /// New Nightwave challenges
+    pub nightwave: NightwaveConfig,
+
+    /// The weekly Duviri Circuit rotation
+    pub duviri_circuit: DuviriCircuitConfig,
+
+    /// Teshin's weekly Steel Path Honors reward rotating in
+    #[default("🛡️ @{channel_name}, Teshin's weekly reward is {reward}! It rotates in {reset}".into())]
+    pub steel_path_reward: ListenerConfigOptions,
+
+    /// The daily Steel Path incursions rotating in
+    #[default("🩸 @{channel_name}, new Steel Path incursions are up! They reset in {reset}".into())]
+    pub steel_path_incursions: ListenerConfigOptions,
+
+    /// Darvo's daily deal
+    #[default(DealConfig::new(
+        "💸 @{channel_name}, Darvo sells {item} for {sale_price}p instead of {original_price}p (-{discount}%)! Only {stock} left",
+        0
+    ))]
+    pub daily_deals: DealConfig,
+
+    /// Discounted items in the in-game market. Unlike daily deals, these have no original price
+    /// or stock.
+    #[default(DealConfig::new(
+        "🏷️ @{channel_name}, flash sale: {item} for {sale_price}p (-{discount}%)! Ends in {expiry}",
+        0
+    ))]
+    pub flash_sales: DealConfig,
+
+    /// Global boosters, like double affinity
+    #[default("🚀 @{channel_name}, global booster active: {title}! Ends in {expiry}".into())]
+    pub global_boosters: ListenerConfigOptions,
+
+    /// In-game events and operations
+    #[default("🎉 @{channel_name}, new event: {title}! Ends in {expiry}".into())]
+    pub events: ListenerConfigOptions,
+
+    /// News posts
+    pub news: NewsConfig,
+
+    /// The daily Sortie rotating in
+    #[default("⚔️ @{channel_name}, new Sortie against {boss} ({faction}): {stages}".into())]
+    pub sorties: ListenerConfigOptions,
+
+    /// The weekly Archon Hunt rotating in
+    #[default("🐉 @{channel_name}, new Archon Hunt against {boss}: {stages}".into())]
+    pub archon_hunts: ListenerConfigOptions,
+
+    /// Quiet hours and live-only notifications, for every listener above
+    pub schedule: ScheduleConfig,
 }
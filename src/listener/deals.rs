@@ -0,0 +1,126 @@
+use anyhow::Error;
+use warframe::worldstate::{
+    Change,
+    TimedEvent,
+    queryable::{
+        DailyDeal,
+        FlashSale,
+    },
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+pub struct DailyDealListener;
+
+impl Listener for DailyDealListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, DailyDeal, _>(daily_deal_callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn daily_deal_callback(state: State, deal: &DailyDeal, change: Change) {
+    if change != Change::Added {
+        return;
+    }
+
+    let discount = deal.discount.max(0) as u32;
+
+    let item = placeholders::Item(deal.item.as_ref());
+    let original_price = placeholders::OriginalPrice(deal.original_price.to_string());
+    let sale_price = placeholders::SalePrice(deal.sale_price.to_string());
+    let discount_placeholder = placeholders::Discount(discount.to_string());
+    let stock = placeholders::Stock((deal.total - deal.sold).max(0).to_string());
+    let expiry = placeholders::Expiry::until(deal.expiry());
+
+    state
+        .send_listener_message(
+            &format!(
+                "daily_deals:{}:{}",
+                deal.unique_name,
+                deal.expiry().timestamp()
+            ),
+            |cfg| {
+                let config = &cfg.daily_deals;
+
+                (config.options.enabled && discount >= config.min_discount).then(|| {
+                    apply_placeholders(
+                        &config.options.format,
+                        [
+                            &item as &dyn Placeholder,
+                            &original_price,
+                            &sale_price,
+                            &discount_placeholder,
+                            &stock,
+                            &expiry,
+                        ],
+                    )
+                })
+            },
+        )
+        .await;
+}
+
+pub struct FlashSaleListener;
+
+impl Listener for FlashSaleListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, FlashSale, _>(flash_sale_callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn flash_sale_callback(state: State, sale: &FlashSale, change: Change) {
+    // most "flash sales" are permanent market entries without a discount
+    if change != Change::Added || sale.discount <= 0 {
+        return;
+    }
+
+    let discount = sale.discount as u32;
+
+    let item = placeholders::Item(sale.item.as_ref());
+    let sale_price = placeholders::SalePrice(sale.premium_override.to_string());
+    let discount_placeholder = placeholders::Discount(discount.to_string());
+    let expiry = placeholders::Expiry::until(sale.expiry());
+
+    state
+        .send_listener_message(
+            &format!(
+                "flash_sales:{}:{}",
+                sale.item.as_ref(),
+                sale.expiry().timestamp()
+            ),
+            |cfg| {
+                let config = &cfg.flash_sales;
+
+                (config.options.enabled && discount >= config.min_discount).then(|| {
+                    apply_placeholders(
+                        &config.options.format,
+                        [
+                            &item as &dyn Placeholder,
+                            &sale_price,
+                            &discount_placeholder,
+                            &expiry,
+                        ],
+                    )
+                })
+            },
+        )
+        .await;
+}
@@ -0,0 +1,104 @@
+use chrono::{
+    DateTime,
+    Datelike,
+    Days,
+    Utc,
+};
+use serde::Deserialize;
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+        polling::Poller,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+/// Duviri's cycle, including the weekly Circuit rotation. Not covered by the worldstate client.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DuviriCycle {
+    pub choices: Vec<CircuitChoices>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CircuitChoices {
+    /// `normal` for warframes, `hard` for the Steel Path incarnon adapters
+    pub category: String,
+    pub choices: Vec<String>,
+}
+
+impl DuviriCycle {
+    fn choices(&self, category: &str) -> &[String] {
+        self.choices
+            .iter()
+            .find(|choices| choices.category == category)
+            .map(|choices| choices.choices.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// The Circuit rotates every Monday at 00:00 UTC
+fn next_weekly_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    let days = 7 - u64::from(now.weekday().num_days_from_monday());
+
+    (now.date_naive() + Days::new(days))
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+}
+
+pub struct DuviriCircuitListener;
+
+impl Listener for DuviriCircuitListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut poller = Poller::<DuviriCycle>::new("duviriCycle");
+
+        loop {
+            let (before, cycle) = poller.next_update().await;
+            callback(&state, &before, &cycle).await;
+        }
+    }
+}
+
+async fn callback(state: &State, before: &DuviriCycle, cycle: &DuviriCycle) {
+    // the cycle itself changes every few hours, the choices only weekly
+    if before.choices == cycle.choices {
+        return;
+    }
+
+    let incarnons = cycle.choices("hard");
+
+    let warframes = placeholders::Warframes(cycle.choices("normal").join(", "));
+    let all_incarnons = placeholders::Incarnons(incarnons.join(", "));
+    let resets_at = next_weekly_reset(Utc::now());
+    let reset = placeholders::Reset::until(resets_at);
+
+    state
+        .send_listener_message(
+            &format!("duviri_circuit:{}", resets_at.timestamp()),
+            |cfg| {
+                let config = &cfg.duviri_circuit;
+
+                let watched = config.incarnon_watchlist.is_empty()
+                    || incarnons.iter().any(|incarnon| {
+                        config
+                            .incarnon_watchlist
+                            .iter()
+                            .any(|watched| incarnon.eq_ignore_ascii_case(watched))
+                    });
+
+                (config.options.enabled && watched).then(|| {
+                    apply_placeholders(
+                        &config.options.format,
+                        [&warframes as &dyn Placeholder, &all_incarnons, &reset],
+                    )
+                })
+            },
+        )
+        .await
+}
@@ -1,11 +1,19 @@
+use std::time::Duration;
+
 use anyhow::Error;
+use chrono::Utc;
 use warframe::worldstate::{
     CetusState,
+    TimedEvent,
     queryable::Cetus,
 };
 
 use crate::{
-    listener::Listener,
+    listener::{
+        Listener,
+        placeholders,
+        warnings::send_warnings,
+    },
     placeholder::Placeholder,
     state::State,
 };
@@ -22,14 +30,59 @@ impl Listener for EidolonHuntListener {
     }
 }
 
-async fn callback(state: State, _before: &Cetus, cetus: &Cetus) {
+async fn callback(state: State, before: &Cetus, cetus: &Cetus) {
     if cetus.state == CetusState::Night {
         state
-            .send_listener_response::<&dyn Placeholder>(
-                &state.listener_cfg().eidolon_hunts.format,
-                [],
+            .send_listener_response(
+                &format!("eidolon_hunts:{}", cetus.expiry().timestamp()),
+                |cfg| &cfg.eidolon_hunts.options,
+                [&placeholders::TimeLeft::until(cetus.expiry()) as &dyn Placeholder],
             )
-            .await
-            .unwrap();
+            .await;
+    } else if before.state == CetusState::Night {
+        // during the day, the expiry is when the next night starts
+        state
+            .send_listener_end(
+                &format!("eidolon_hunts_end:{}", before.expiry().timestamp()),
+                |cfg| Some(&cfg.eidolon_hunts),
+                [&placeholders::TimeLeft::until(cetus.expiry()) as &dyn Placeholder],
+            )
+            .await;
+    }
+}
+
+/// How long to wait before fetching the Cetus cycle again, if it failed or wasn't updated yet
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+pub struct EidolonHuntWarningListener;
+
+impl Listener for EidolonHuntWarningListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        loop {
+            let cetus = match state.wf.fetch::<Cetus>().await {
+                Ok(cetus) => cetus,
+                Err(error) => {
+                    tracing::warn!("Failed to fetch the Cetus cycle: {error:#}");
+                    tokio::time::sleep(RETRY_INTERVAL).await;
+                    continue;
+                }
+            };
+
+            // during the day, the expiry is when night starts
+            if cetus.state == CetusState::Day {
+                send_warnings(
+                    &state,
+                    &format!("eidolon_hunts_warning:{}", cetus.expiry().timestamp()),
+                    cetus.expiry(),
+                    |cfg| Some(&cfg.eidolon_hunts_warning),
+                    &[],
+                )
+                .await;
+            }
+
+            // the worldstate takes a moment to catch up after the cycle changed
+            let until_next_cycle = (cetus.expiry() - Utc::now()).to_std().unwrap_or_default();
+            tokio::time::sleep(until_next_cycle.max(RETRY_INTERVAL)).await;
+        }
     }
 }
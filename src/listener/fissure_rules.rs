@@ -0,0 +1,187 @@
+use anyhow::Error;
+use arbitration_data::model::mapped::MAP_RANKING;
+use warframe::worldstate::{
+    Change,
+    Tier,
+    TimedEvent,
+    queryable::Fissure,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        config::FissureRule,
+        meta_relics::extract_node,
+        placeholders,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+/// The parts of a fissure a [`FissureRule`] can match on
+pub struct FissureInfo<'a> {
+    pub mission_type: &'a str,
+    pub tier: &'a Tier,
+    pub is_hard: bool,
+    pub is_storm: bool,
+    /// The node's name, without the planet
+    pub node: &'a str,
+    pub planet: &'a str,
+    pub faction: &'a str,
+    pub map_tier: Option<&'a arbitration_data::Tier>,
+}
+
+impl FissureRule {
+    pub fn matches(&self, fissure: &FissureInfo) -> bool {
+        fn any_name(names: &[String], name: &str) -> bool {
+            names.is_empty() || names.iter().any(|n| n.eq_ignore_ascii_case(name))
+        }
+
+        any_name(&self.mission_types, fissure.mission_type)
+            && (self.relic_tiers.is_empty()
+                || self
+                    .relic_tiers
+                    .iter()
+                    .any(|tier| tier.matches(fissure.tier)))
+            && self
+                .is_hard
+                .is_none_or(|is_hard| is_hard == fissure.is_hard)
+            && self
+                .is_storm
+                .is_none_or(|is_storm| is_storm == fissure.is_storm)
+            && any_name(&self.nodes, fissure.node)
+            && any_name(&self.planets, fissure.planet)
+            && any_name(&self.factions, fissure.faction)
+            && (self.map_tiers.is_empty()
+                || fissure.map_tier.is_some_and(|map_tier| {
+                    self.map_tiers
+                        .iter()
+                        .any(|tier| arbitration_data::Tier::from(*tier) == *map_tier)
+                }))
+    }
+}
+
+pub struct FissureRulesListener;
+
+impl Listener for FissureRulesListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, Fissure, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, fissure: &Fissure, change: Change) {
+    if change != Change::Added {
+        return;
+    }
+
+    // fissure.node_key contains a formatted node, including the planet.
+    let node = extract_node(&fissure.node_key).unwrap_or(&fissure.node_key);
+    let planet = extract_planet(&fissure.node_key).unwrap_or_default();
+
+    let info = FissureInfo {
+        mission_type: &fissure.mission_type,
+        tier: &fissure.tier,
+        is_hard: fissure.is_hard,
+        is_storm: fissure.is_storm,
+        node,
+        planet,
+        faction: &fissure.enemy,
+        map_tier: MAP_RANKING.get(node),
+    };
+
+    let placeholders = [
+        &placeholders::Node(&fissure.node) as &dyn Placeholder,
+        &placeholders::Planet(planet),
+        &placeholders::MissionType(&fissure.mission_type),
+        &placeholders::Tier(&fissure.tier_name),
+        &placeholders::Difficulty {
+            is_hard: fissure.is_hard,
+        },
+        &placeholders::Faction(&fissure.enemy),
+        &placeholders::TimeLeft::until(fissure.expiry()),
+    ];
+
+    state
+        .send_listener_message(&format!("fissure_rules:{}", fissure.id), |cfg| {
+            let rule = cfg
+                .fissure_rules
+                .iter()
+                .find(|rule| rule.options.enabled && rule.matches(&info))?;
+
+            Some(apply_placeholders(&rule.options.format, placeholders))
+        })
+        .await;
+}
+
+fn extract_planet(node: &str) -> Option<&str> {
+    node.rsplit_once(" (")
+        .and_then(|(_, planet)| planet.strip_suffix(')'))
+}
+
+#[cfg(test)]
+mod tests {
+    use warframe::worldstate::Tier;
+
+    use crate::listener::{
+        config::{
+            ArbitrationTier,
+            FissureRule,
+        },
+        fissure_rules::{
+            FissureInfo,
+            extract_planet,
+        },
+    };
+
+    #[test]
+    fn test_planet_matcher() {
+        assert_eq!(extract_planet("Yuvarium (Lua)"), Some("Lua"));
+        assert_eq!(extract_planet("R-9 Cloud (Veil)"), Some("Veil"));
+        assert_eq!(extract_planet("Yuvarium"), None);
+    }
+
+    #[test]
+    fn test_rule_matching() {
+        let rule: FissureRule = serde_json::from_value(serde_json::json!({
+            "enabled": true,
+            "format": "",
+            "mission_types": ["defense"],
+            "is_hard": true,
+            "map_tiers": ["S", "A"]
+        }))
+        .unwrap();
+
+        let map_tier = arbitration_data::Tier::from(ArbitrationTier::S);
+        let fissure = FissureInfo {
+            mission_type: "Defense",
+            tier: &Tier::Lith,
+            is_hard: true,
+            is_storm: false,
+            node: "Yuvarium",
+            planet: "Lua",
+            faction: "Corrupted",
+            map_tier: Some(&map_tier),
+        };
+
+        assert!(rule.matches(&fissure));
+        assert!(!rule.matches(&FissureInfo {
+            is_hard: false,
+            ..fissure
+        }));
+        assert!(!rule.matches(&FissureInfo {
+            map_tier: None,
+            ..fissure
+        }));
+        assert!(!rule.matches(&FissureInfo {
+            mission_type: "Survival",
+            ..fissure
+        }));
+    }
+}
@@ -0,0 +1,97 @@
+use warframe::worldstate::{
+    Change,
+    queryable::Invasion,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+        polling::{
+            Poller,
+            nested_changes,
+        },
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+pub struct InvasionListener;
+
+impl Listener for InvasionListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        // invasions have no expiry, so the nested updates of the worldstate client don't work
+        let mut poller = Poller::<Vec<Invasion>>::new("invasions");
+
+        loop {
+            let (before, invasions) = poller.next_update().await;
+
+            for (invasion, change) in nested_changes(&before, &invasions, invasion_id) {
+                callback(&state, invasion, change).await;
+            }
+        }
+    }
+}
+
+/// Invasions have no id, but only one can run on a node at a time
+fn invasion_id(invasion: &Invasion) -> String {
+    format!("{}:{}", invasion.node_key, invasion.activation.timestamp())
+}
+
+async fn callback(state: &State, invasion: &Invasion, change: Change) {
+    if change != Change::Added || invasion.completed {
+        return;
+    }
+
+    // (name, count) of every reward on both sides
+    let rewards = [&invasion.attacker, &invasion.defender]
+        .into_iter()
+        .filter_map(|side| side.reward.as_ref())
+        .flat_map(|reward| {
+            reward
+                .counted_items
+                .iter()
+                .map(|item| (item.r#type.as_str(), item.count.max(0) as u32))
+                .chain(reward.items.iter().map(|item| (item.as_str(), 1)))
+        })
+        .collect::<Vec<_>>();
+
+    let node = placeholders::Node(&invasion.node);
+    let attacker = placeholders::Attacker(&invasion.attacker.faction);
+    let defender = placeholders::Defender(&invasion.defender.faction);
+
+    state
+        .send_listener_message(&format!("invasions:{}", invasion_id(invasion)), |cfg| {
+            let config = &cfg.invasions;
+
+            if !config.options.enabled {
+                return None;
+            }
+
+            let watched_rewards = rewards
+                .iter()
+                .filter(|(name, count)| {
+                    config
+                        .watchlist
+                        .iter()
+                        .any(|item| item.matches(name, *count))
+                })
+                .map(|(name, count)| format!("{count}x {name}"))
+                .collect::<Vec<_>>();
+
+            if watched_rewards.is_empty() {
+                return None;
+            }
+
+            let reward = placeholders::Reward(watched_rewards.join(" + "));
+
+            Some(apply_placeholders(
+                &config.options.format,
+                [&node as &dyn Placeholder, &attacker, &defender, &reward],
+            ))
+        })
+        .await;
+}
@@ -33,7 +33,7 @@ impl Listener for MetaRelicsListener {
 }
 
 async fn callback(state: State, fissure: &Fissure, change: Change) {
-    if change != Change::Added || fissure.mission_key != MissionType::Defense {
+    if fissure.mission_key != MissionType::Defense {
         return;
     }
 
@@ -45,24 +45,40 @@ async fn callback(state: State, fissure: &Fissure, change: Change) {
 
     match MAP_RANKING.get(node) {
         Some(tier) if *tier == arbitration_data::Tier::S || *tier == arbitration_data::Tier::A => {
-            state
-                .send_listener_response(
-                    &state.listener_cfg().meta_relics.format,
-                    [
-                        &placeholders::Node(&fissure.node) as &dyn Placeholder,
-                        &placeholders::Difficulty {
-                            is_hard: fissure.is_hard,
-                        },
-                    ],
-                )
-                .await
-                .unwrap();
+            let placeholders = [
+                &placeholders::Node(&fissure.node) as &dyn Placeholder,
+                &placeholders::Difficulty {
+                    is_hard: fissure.is_hard,
+                },
+            ];
+
+            match change {
+                Change::Added => {
+                    state
+                        .send_listener_response(
+                            &format!("meta_relics:{}", fissure.id),
+                            |cfg| &cfg.meta_relics.options,
+                            placeholders,
+                        )
+                        .await
+                }
+                Change::Removed => {
+                    state
+                        .send_listener_end(
+                            &format!("meta_relics_end:{}", fissure.id),
+                            |cfg| Some(&cfg.meta_relics),
+                            placeholders,
+                        )
+                        .await
+                }
+            }
         }
         _ => (),
     }
 }
 
-fn extract_node(node: &str) -> Option<&str> {
+/// Extracts the node's name from a node in the `Node (Planet)` format
+pub fn extract_node(node: &str) -> Option<&str> {
     NODE_MATCHER
         .captures(node)
         .and_then(|captures| captures.get(1).map(|m| m.as_str()))
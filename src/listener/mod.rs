@@ -1,15 +1,56 @@
+pub mod arbitrations;
+pub mod archon_hunts;
+pub mod bounties;
+pub mod cambion_drift;
 pub mod config;
+pub mod deals;
+pub mod duviri_circuit;
 pub mod eidolon_hunts;
+pub mod fissure_rules;
+pub mod invasions;
 pub mod meta_relics;
+pub mod news;
+pub mod nightwave;
+pub mod orb_vallis;
+pub mod persistent_enemies;
 pub mod placeholders;
+pub mod polling;
 mod register;
-pub mod s_tier_arbitrations;
+pub mod schedule;
+pub mod sorties;
+pub mod steel_path;
 pub mod steel_path_disruption_fissures;
+pub mod void_storms;
+pub mod void_trader;
+pub mod warnings;
+pub mod zariman;
 
-use crate::state::State;
+use std::{
+    sync::Arc,
+    time::Duration,
+};
+
+use tokio::sync::watch;
+
+use crate::{
+    config::Settings,
+    state::State,
+};
 
 pub trait Listener {
     fn run(state: State) -> impl Future<Output = anyhow::Result<()>> + Send;
 }
 
-pub use register::register_listeners;
+/// Sleeps for `duration`. Returns `false` early if the settings changed in the meantime, so
+/// listeners can recompute what they were waiting for.
+pub async fn sleep_unless_reloaded(
+    settings: &mut watch::Receiver<Arc<Settings>>,
+    duration: Duration,
+) -> bool {
+    tokio::select! {
+        () = tokio::time::sleep(duration) => true,
+        Ok(()) = settings.changed() => false,
+    }
+}
+
+pub use register::run_listeners;
@@ -0,0 +1,161 @@
+use anyhow::Error;
+use serde::Deserialize;
+use warframe::worldstate::{
+    Change,
+    TimedEvent,
+    queryable::{
+        Event,
+        GlobalUpgrade,
+    },
+};
+
+use crate::{
+    listener::{
+        Listener,
+        config::NewsTag,
+        placeholders,
+        polling::Poller,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+/// A news post. Not the worldstate client's [`warframe::worldstate::queryable::News`], as that
+/// lacks the post's `link` and the `prime_access` flag.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewsItem {
+    pub id: String,
+    pub message: String,
+    pub link: String,
+    pub update: bool,
+    pub prime_access: bool,
+    pub stream: bool,
+}
+
+impl NewsItem {
+    pub fn tag(&self) -> NewsTag {
+        if self.update {
+            NewsTag::Update
+        } else if self.prime_access {
+            NewsTag::PrimeAccess
+        } else if self.stream {
+            NewsTag::Stream
+        } else {
+            NewsTag::Other
+        }
+    }
+}
+
+pub struct GlobalBoosterListener;
+
+impl Listener for GlobalBoosterListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, GlobalUpgrade, _>(
+                booster_callback,
+                state.clone(),
+            )
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn booster_callback(state: State, booster: &GlobalUpgrade, change: Change) {
+    if change != Change::Added {
+        return;
+    }
+
+    // e.g. `Affinity x2`
+    let title = format!(
+        "{} {}{}",
+        booster.upgrade, booster.operation_symbol, booster.upgrade_operation_value
+    );
+
+    state
+        .send_listener_response(
+            &format!(
+                "global_boosters:{}:{}",
+                booster.activation().timestamp(),
+                booster.upgrade
+            ),
+            |cfg| &cfg.global_boosters,
+            [
+                &placeholders::Title(&title) as &dyn Placeholder,
+                &placeholders::Expiry::until(booster.expiry()),
+            ],
+        )
+        .await;
+}
+
+pub struct EventListener;
+
+impl Listener for EventListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, Event, _>(event_callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn event_callback(state: State, event: &Event, change: Change) {
+    if change != Change::Added {
+        return;
+    }
+
+    let description = event.description.as_deref().unwrap_or("Event");
+
+    // the score changing shows up as a removed and added event, so the key leaves it out
+    state
+        .send_listener_response(
+            &format!("events:{}:{description}", event.activation().timestamp()),
+            |cfg| &cfg.events,
+            [
+                &placeholders::Title(description) as &dyn Placeholder,
+                &placeholders::Expiry::until(event.expiry()),
+            ],
+        )
+        .await;
+}
+
+pub struct NewsListener;
+
+impl Listener for NewsListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut poller = Poller::<Vec<NewsItem>>::new("news");
+
+        loop {
+            let (before, news) = poller.next_update().await;
+
+            for item in news
+                .iter()
+                .filter(|item| !before.iter().any(|known| known.id == item.id))
+            {
+                let tag = item.tag();
+                let title = placeholders::Title(&item.message);
+                let link = placeholders::Link(&item.link);
+
+                state
+                    .send_listener_message(&format!("news:{}", item.id), |cfg| {
+                        let config = &cfg.news;
+
+                        (config.options.enabled
+                            && (config.tags.is_empty() || config.tags.contains(&tag)))
+                        .then(|| {
+                            apply_placeholders(
+                                &config.options.format,
+                                [&title as &dyn Placeholder, &link],
+                            )
+                        })
+                    })
+                    .await;
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use warframe::worldstate::{
+    Change,
+    TimedEvent,
+    queryable::Nightwave,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+        polling::{
+            Poller,
+            nested_changes,
+        },
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+pub struct NightwaveListener;
+
+impl Listener for NightwaveListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        // the worldstate client only reports an update when the season's expiry changes, which
+        // misses the challenges rotating in between. Polling catches every change to the
+        // challenge list, `nested_changes` picks out the new ones.
+        let mut poller = Poller::<Nightwave>::new("nightwave");
+
+        loop {
+            let (before, nightwave) = poller.next_update().await;
+            callback(&state, &before, &nightwave).await;
+        }
+    }
+}
+
+async fn callback(state: &State, before: &Nightwave, nightwave: &Nightwave) {
+    let changes = nested_changes(
+        &before.active_challenges,
+        &nightwave.active_challenges,
+        |challenge| challenge.id.clone(),
+    );
+
+    for (challenge, change) in changes {
+        if change != Change::Added {
+            continue;
+        }
+
+        let kind = if challenge.is_daily {
+            "Daily"
+        } else if challenge.is_elite {
+            "Elite Weekly"
+        } else {
+            "Weekly"
+        };
+
+        let title = placeholders::Title(&challenge.title);
+        let description = placeholders::Description(&challenge.description);
+        let standing = placeholders::Standing(challenge.reputation.to_string());
+        let kind = placeholders::ChallengeKind(kind);
+        let time_left = placeholders::TimeLeft::until(challenge.expiry());
+
+        state
+            .send_listener_message(&format!("nightwave:{}", challenge.id), |cfg| {
+                let config = &cfg.nightwave;
+
+                let wanted =
+                    !config.only_elite_weeklies || (challenge.is_elite && !challenge.is_daily);
+
+                (config.options.enabled && wanted).then(|| {
+                    apply_placeholders(
+                        &config.options.format,
+                        [
+                            &title as &dyn Placeholder,
+                            &description,
+                            &standing,
+                            &kind,
+                            &time_left,
+                        ],
+                    )
+                })
+            })
+            .await;
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Error;
+use warframe::worldstate::{
+    OrbVallisState,
+    TimedEvent,
+    queryable::OrbVallis,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+    },
+    placeholder::Placeholder,
+    state::State,
+};
+
+pub struct OrbVallisWarmListener;
+
+impl Listener for OrbVallisWarmListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_update_with_state::<_, OrbVallis, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, before: &OrbVallis, orb_vallis: &OrbVallis) {
+    if before.state != OrbVallisState::Warm && orb_vallis.state == OrbVallisState::Warm {
+        state
+            .send_listener_response(
+                &format!("orb_vallis_warm:{}", orb_vallis.expiry().timestamp()),
+                |cfg| &cfg.orb_vallis_warm.options,
+                [&placeholders::TimeLeft::until(orb_vallis.expiry()) as &dyn Placeholder],
+            )
+            .await;
+    } else if before.state == OrbVallisState::Warm && orb_vallis.state != OrbVallisState::Warm {
+        state
+            .send_listener_end(
+                &format!("orb_vallis_warm_end:{}", before.expiry().timestamp()),
+                |cfg| Some(&cfg.orb_vallis_warm),
+                [&placeholders::TimeLeft::until(orb_vallis.expiry()) as &dyn Placeholder],
+            )
+            .await;
+    }
+}
@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use warframe::worldstate::Change;
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+        polling::{
+            Poller,
+            nested_changes,
+        },
+    },
+    placeholder::Placeholder,
+    state::State,
+};
+
+/// An Acolyte or another persistent enemy roaming the star chart. Not covered by the worldstate
+/// client.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistentEnemy {
+    pub id: String,
+    /// e.g. `Angst`
+    pub agent_type: String,
+    /// e.g. `Tikal (Mars)`
+    pub last_discovered_at: String,
+    pub is_discovered: bool,
+    /// Between 0 and 1
+    pub health_percent: f64,
+}
+
+pub struct PersistentEnemyListener;
+
+impl Listener for PersistentEnemyListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut poller = Poller::<Vec<PersistentEnemy>>::new("persistentEnemies");
+
+        loop {
+            let (before, enemies) = poller.next_update().await;
+
+            for (enemy, change) in nested_changes(&before, &enemies, |enemy| enemy.id.clone()) {
+                if change == Change::Removed {
+                    state
+                        .send_listener_response(
+                            &format!("persistent_enemies_defeated:{}", enemy.id),
+                            |cfg| &cfg.persistent_enemies_defeated,
+                            [&placeholders::Enemy(&enemy.agent_type) as &dyn Placeholder],
+                        )
+                        .await;
+                }
+            }
+
+            // enemies move around, so every new location counts as a discovery
+            for enemy in enemies.iter().filter(|enemy| {
+                enemy.is_discovered
+                    && !before.iter().any(|known| {
+                        known.id == enemy.id
+                            && known.is_discovered
+                            && known.last_discovered_at == enemy.last_discovered_at
+                    })
+            }) {
+                state
+                    .send_listener_response(
+                        &format!(
+                            "persistent_enemies:{}:{}",
+                            enemy.id, enemy.last_discovered_at
+                        ),
+                        |cfg| &cfg.persistent_enemies,
+                        [
+                            &placeholders::Enemy(&enemy.agent_type) as &dyn Placeholder,
+                            &placeholders::Node(&enemy.last_discovered_at),
+                            &placeholders::Health(format!("{:.0}", enemy.health_percent * 100.0)),
+                        ],
+                    )
+                    .await;
+            }
+        }
+    }
+}
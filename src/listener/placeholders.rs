@@ -1,5 +1,54 @@
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use warframe::worldstate;
+
 use crate::placeholder::Placeholder;
 
+/// The name of a faction, as shown in game
+pub fn faction_name(faction: &worldstate::Faction) -> &'static str {
+    use worldstate::Faction;
+
+    match faction {
+        Faction::Orokin => "Orokin",
+        Faction::Corrupted => "Corrupted",
+        Faction::Infested => "Infested",
+        Faction::Corpus => "Corpus",
+        Faction::Grineer => "Grineer",
+        Faction::Tenno => "Tenno",
+        Faction::Narmer => "Narmer",
+        Faction::Crossfire => "Crossfire",
+        Faction::Murmur => "The Murmur",
+        Faction::ManInTheWall => "Man in the Wall",
+    }
+}
+
+/// Formats the time left until `time` for chat, see [`format_duration`].
+///
+/// Rounded to the nearest minute, as this is usually computed just after a sleep that woke up a
+/// moment too late for truncating, e.g. 4m 59s before something starts
+pub fn format_time_until(time: DateTime<Utc>) -> String {
+    format_duration(round_to_minutes(time - Utc::now()))
+}
+
+fn round_to_minutes(duration: TimeDelta) -> TimeDelta {
+    TimeDelta::minutes((duration.num_seconds() + 30).div_euclid(60))
+}
+
+/// Formats a duration for chat, e.g. `2d 3h`, `1h 5m` or `42m`
+fn format_duration(duration: TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / (24 * 60), minutes / 60 % 24, minutes % 60);
+
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
 pub struct Node<'a>(pub &'a str);
 
 impl<'a> Placeholder for Node<'a> {
@@ -24,6 +73,30 @@ impl<'a> Placeholder for Planet<'a> {
     }
 }
 
+pub struct Tier<'a>(pub &'a str);
+
+impl<'a> Placeholder for Tier<'a> {
+    fn key(&self) -> &'static str {
+        "{tier}"
+    }
+
+    fn value(&self) -> &'a str {
+        self.0
+    }
+}
+
+pub struct MissionType<'a>(pub &'a str);
+
+impl<'a> Placeholder for MissionType<'a> {
+    fn key(&self) -> &'static str {
+        "{mission_type}"
+    }
+
+    fn value(&self) -> &'a str {
+        self.0
+    }
+}
+
 pub struct Difficulty {
     pub is_hard: bool,
 }
@@ -37,3 +110,564 @@ impl Placeholder for Difficulty {
         if self.is_hard { "Steel Path" } else { "Normal" }
     }
 }
+
+/// The time left until something expires, see [`format_time_until`]
+pub struct Expiry(pub String);
+
+impl Expiry {
+    pub fn until(expiry: DateTime<Utc>) -> Self {
+        Self(format_time_until(expiry))
+    }
+}
+
+impl Placeholder for Expiry {
+    fn key(&self) -> &'static str {
+        "{expiry}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Relay<'a>(pub &'a str);
+
+impl Placeholder for Relay<'_> {
+    fn key(&self) -> &'static str {
+        "{relay}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+/// The time left until Baro leaves, see [`format_time_until`]
+pub struct Departure(pub String);
+
+impl Departure {
+    pub fn until(departure: DateTime<Utc>) -> Self {
+        Self(format_time_until(departure))
+    }
+}
+
+impl Placeholder for Departure {
+    fn key(&self) -> &'static str {
+        "{departure}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Inventory(pub String);
+
+impl Placeholder for Inventory {
+    fn key(&self) -> &'static str {
+        "{inventory}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Boss<'a>(pub &'a str);
+
+impl Placeholder for Boss<'_> {
+    fn key(&self) -> &'static str {
+        "{boss}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Faction<'a>(pub &'a str);
+
+impl Placeholder for Faction<'_> {
+    fn key(&self) -> &'static str {
+        "{faction}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+/// A single stage of a Sortie or an Archon Hunt
+pub struct Stage<'a> {
+    pub mission_type: &'a str,
+    pub node: &'a str,
+    pub modifier: Option<&'a str>,
+}
+
+impl<'a> Stage<'a> {
+    /// The placeholders of this stage, numbered by `number` (starting at 1). Only the first three
+    /// stages have placeholders.
+    pub fn placeholders(&self, number: usize) -> Vec<StagePlaceholder<'a>> {
+        const MISSION_TYPES: [&str; 3] =
+            ["{mission_type_1}", "{mission_type_2}", "{mission_type_3}"];
+        const NODES: [&str; 3] = ["{node_1}", "{node_2}", "{node_3}"];
+        const MODIFIERS: [&str; 3] = ["{modifier_1}", "{modifier_2}", "{modifier_3}"];
+
+        let Some(index) = number.checked_sub(1).filter(|index| *index < 3) else {
+            return Vec::new();
+        };
+
+        let mut placeholders = vec![
+            StagePlaceholder(MISSION_TYPES[index], self.mission_type),
+            StagePlaceholder(NODES[index], self.node),
+        ];
+
+        if let Some(modifier) = self.modifier {
+            placeholders.push(StagePlaceholder(MODIFIERS[index], modifier));
+        }
+
+        placeholders
+    }
+}
+
+pub struct StagePlaceholder<'a>(&'static str, &'a str);
+
+impl Placeholder for StagePlaceholder<'_> {
+    fn key(&self) -> &'static str {
+        self.0
+    }
+
+    fn value(&self) -> &str {
+        self.1
+    }
+}
+
+/// Every stage in a single line, e.g. `Spy on Tikal (Mars) | Defense on ...`
+pub struct Stages(pub String);
+
+impl Stages {
+    pub fn new(stages: &[Stage]) -> Self {
+        let stages = stages
+            .iter()
+            .map(|stage| match stage.modifier {
+                Some(modifier) => {
+                    format!("{} on {} ({})", stage.mission_type, stage.node, modifier)
+                }
+                None => format!("{} on {}", stage.mission_type, stage.node),
+            })
+            .collect::<Vec<_>>();
+
+        Self(stages.join(" | "))
+    }
+}
+
+impl Placeholder for Stages {
+    fn key(&self) -> &'static str {
+        "{stages}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Every placeholder of a Sortie or an Archon Hunt: `{boss}`, `{faction}`, `{stages}` and the
+/// placeholders of each [`Stage`]
+pub struct StagedMission<'a> {
+    boss: Boss<'a>,
+    faction: Faction<'a>,
+    stages: Stages,
+    stage_placeholders: Vec<StagePlaceholder<'a>>,
+}
+
+impl<'a> StagedMission<'a> {
+    pub fn new(boss: &'a str, faction: &'a str, stages: &[Stage<'a>]) -> Self {
+        Self {
+            boss: Boss(boss),
+            faction: Faction(faction),
+            stages: Stages::new(stages),
+            stage_placeholders: stages
+                .iter()
+                .enumerate()
+                .flat_map(|(index, stage)| stage.placeholders(index + 1))
+                .collect(),
+        }
+    }
+
+    pub fn placeholders(&self) -> impl Iterator<Item = &dyn Placeholder> {
+        [&self.boss as &dyn Placeholder, &self.faction, &self.stages]
+            .into_iter()
+            .chain(
+                self.stage_placeholders
+                    .iter()
+                    .map(|p| p as &dyn Placeholder),
+            )
+    }
+}
+
+/// The time left until something ends, see [`format_time_until`]
+pub struct TimeLeft(pub String);
+
+impl TimeLeft {
+    pub fn until(expiry: DateTime<Utc>) -> Self {
+        Self(format_time_until(expiry))
+    }
+}
+
+impl Placeholder for TimeLeft {
+    fn key(&self) -> &'static str {
+        "{time_left}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The time until something resets, see [`format_time_until`]
+pub struct Reset(pub String);
+
+impl Reset {
+    pub fn until(reset: DateTime<Utc>) -> Self {
+        Self(format_time_until(reset))
+    }
+}
+
+impl Placeholder for Reset {
+    fn key(&self) -> &'static str {
+        "{reset}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct CycleState<'a>(pub &'a str);
+
+impl Placeholder for CycleState<'_> {
+    fn key(&self) -> &'static str {
+        "{state}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Title<'a>(pub &'a str);
+
+impl Placeholder for Title<'_> {
+    fn key(&self) -> &'static str {
+        "{title}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Description<'a>(pub &'a str);
+
+impl Placeholder for Description<'_> {
+    fn key(&self) -> &'static str {
+        "{description}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Standing(pub String);
+
+impl Placeholder for Standing {
+    fn key(&self) -> &'static str {
+        "{standing}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// `Daily`, `Weekly` or `Elite Weekly`
+pub struct ChallengeKind<'a>(pub &'a str);
+
+impl Placeholder for ChallengeKind<'_> {
+    fn key(&self) -> &'static str {
+        "{kind}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Warframes(pub String);
+
+impl Placeholder for Warframes {
+    fn key(&self) -> &'static str {
+        "{warframes}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Incarnons(pub String);
+
+impl Placeholder for Incarnons {
+    fn key(&self) -> &'static str {
+        "{incarnons}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Item<'a>(pub &'a str);
+
+impl Placeholder for Item<'_> {
+    fn key(&self) -> &'static str {
+        "{item}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct OriginalPrice(pub String);
+
+impl Placeholder for OriginalPrice {
+    fn key(&self) -> &'static str {
+        "{original_price}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct SalePrice(pub String);
+
+impl Placeholder for SalePrice {
+    fn key(&self) -> &'static str {
+        "{sale_price}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A discount in percent, without the `%`
+pub struct Discount(pub String);
+
+impl Placeholder for Discount {
+    fn key(&self) -> &'static str {
+        "{discount}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Stock(pub String);
+
+impl Placeholder for Stock {
+    fn key(&self) -> &'static str {
+        "{stock}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Link<'a>(pub &'a str);
+
+impl Placeholder for Link<'_> {
+    fn key(&self) -> &'static str {
+        "{link}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Syndicate<'a>(pub &'a str);
+
+impl Placeholder for Syndicate<'_> {
+    fn key(&self) -> &'static str {
+        "{syndicate}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Bounty<'a>(pub &'a str);
+
+impl Placeholder for Bounty<'_> {
+    fn key(&self) -> &'static str {
+        "{bounty}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+/// Enemy levels, e.g. `5-15`
+pub struct LevelRange(pub String);
+
+impl Placeholder for LevelRange {
+    fn key(&self) -> &'static str {
+        "{level_range}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Rewards(pub String);
+
+impl Placeholder for Rewards {
+    fn key(&self) -> &'static str {
+        "{rewards}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Enemy<'a>(pub &'a str);
+
+impl Placeholder for Enemy<'_> {
+    fn key(&self) -> &'static str {
+        "{enemy}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+/// Health left in percent, without the `%`
+pub struct Health(pub String);
+
+impl Placeholder for Health {
+    fn key(&self) -> &'static str {
+        "{health}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Count(pub String);
+
+impl Placeholder for Count {
+    fn key(&self) -> &'static str {
+        "{count}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Notifications(pub String);
+
+impl Placeholder for Notifications {
+    fn key(&self) -> &'static str {
+        "{notifications}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Attacker<'a>(pub &'a str);
+
+impl Placeholder for Attacker<'_> {
+    fn key(&self) -> &'static str {
+        "{attacker}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Defender<'a>(pub &'a str);
+
+impl Placeholder for Defender<'_> {
+    fn key(&self) -> &'static str {
+        "{defender}"
+    }
+
+    fn value(&self) -> &str {
+        self.0
+    }
+}
+
+pub struct Reward(pub String);
+
+impl Placeholder for Reward {
+    fn key(&self) -> &'static str {
+        "{reward}"
+    }
+
+    fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use crate::listener::placeholders::{
+        format_duration,
+        round_to_minutes,
+    };
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(TimeDelta::seconds(59)), "0m");
+        assert_eq!(format_duration(TimeDelta::minutes(42)), "42m");
+        assert_eq!(format_duration(TimeDelta::minutes(65)), "1h 5m");
+        assert_eq!(format_duration(TimeDelta::hours(51)), "2d 3h");
+        assert_eq!(format_duration(TimeDelta::minutes(-5)), "0m");
+    }
+
+    #[test]
+    fn test_round_to_minutes() {
+        let lead_time = TimeDelta::minutes(5);
+
+        assert_eq!(
+            round_to_minutes(lead_time - TimeDelta::milliseconds(20)),
+            lead_time
+        );
+        assert_eq!(
+            round_to_minutes(lead_time + TimeDelta::seconds(29)),
+            lead_time
+        );
+        assert_eq!(
+            round_to_minutes(lead_time - TimeDelta::seconds(31)),
+            TimeDelta::minutes(4)
+        );
+    }
+}
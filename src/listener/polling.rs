@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use warframe::worldstate::Change;
+
+/// The worldstate API the `warframe` crate uses as well
+pub const WORLDSTATE_URL: &str = "https://api.warframestat.us/pc";
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Polls a worldstate endpoint the worldstate client doesn't cover, or doesn't report every
+/// update of.
+///
+/// Mirrors `call_on_update_with_state`: [`Poller::next_update`] returns the previous and the
+/// current value whenever the endpoint's data changed.
+pub struct Poller<T> {
+    url: String,
+    client: reqwest::Client,
+    last: Option<T>,
+}
+
+impl<T> Poller<T>
+where
+    T: DeserializeOwned + PartialEq + Clone,
+{
+    /// `endpoint` is relative to [`WORLDSTATE_URL`], e.g. `zarimanCycle`
+    pub fn new(endpoint: &str) -> Self {
+        Self {
+            url: format!("{WORLDSTATE_URL}/{endpoint}/?language=en"),
+            client: reqwest::Client::new(),
+            last: None,
+        }
+    }
+
+    /// Waits until the data changed and returns the previous and the current value
+    pub async fn next_update(&mut self) -> (T, T) {
+        loop {
+            match self.fetch().await {
+                Ok(current) => match self.last.replace(current.clone()) {
+                    Some(before) if before != current => return (before, current),
+                    _ => (),
+                },
+                Err(error) => {
+                    tracing::warn!(url = self.url, "Failed to poll worldstate: {error:#}")
+                }
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn fetch(&self) -> anyhow::Result<T> {
+        Ok(self
+            .client
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+}
+
+/// Mirrors `call_on_nested_update_with_state` for polled lists: returns every item that was
+/// added to or removed from the list, compared by `id`.
+pub fn nested_changes<'a, T, K: PartialEq>(
+    before: &'a [T],
+    current: &'a [T],
+    id: impl Fn(&T) -> K,
+) -> Vec<(&'a T, Change)> {
+    let added = current
+        .iter()
+        .filter(|item| !before.iter().any(|known| id(known) == id(item)))
+        .map(|item| (item, Change::Added));
+
+    let removed = before
+        .iter()
+        .filter(|item| !current.iter().any(|known| id(known) == id(item)))
+        .map(|item| (item, Change::Removed));
+
+    added.chain(removed).collect()
+}
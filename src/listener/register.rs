@@ -1,37 +1,181 @@
-use tokio::task::JoinSet;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+};
+
+use tokio::task::{
+    AbortHandle,
+    JoinSet,
+};
 
 use crate::{
     listener::{
         Listener,
-        eidolon_hunts::EidolonHuntListener,
+        arbitrations::{
+            ArbitrationListener,
+            ArbitrationWarningListener,
+        },
+        archon_hunts::ArchonHuntListener,
+        bounties::BountyListener,
+        cambion_drift::CambionDriftListener,
+        config::ListenerConfig,
+        deals::{
+            DailyDealListener,
+            FlashSaleListener,
+        },
+        duviri_circuit::DuviriCircuitListener,
+        eidolon_hunts::{
+            EidolonHuntListener,
+            EidolonHuntWarningListener,
+        },
+        fissure_rules::FissureRulesListener,
+        invasions::InvasionListener,
         meta_relics::MetaRelicsListener,
-        s_tier_arbitrations::STierArbitrationListener,
+        news::{
+            EventListener,
+            GlobalBoosterListener,
+            NewsListener,
+        },
+        nightwave::NightwaveListener,
+        orb_vallis::OrbVallisWarmListener,
+        persistent_enemies::PersistentEnemyListener,
+        sorties::SortieListener,
+        steel_path::SteelPathListener,
         steel_path_disruption_fissures::SteelPathDisruptionFissuresListener,
+        void_storms::VoidStormListener,
+        void_trader::VoidTraderListener,
+        zariman::ZarimanListener,
     },
-    state,
+    state::State,
 };
 
-pub fn register_listeners(
-    join_set: &mut JoinSet<Result<(), anyhow::Error>>,
-    state: state::State,
-) -> anyhow::Result<()> {
-    let message_config = &state.config.clone().listener_config;
+type ListenerFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
 
-    if message_config.eidolon_hunts.enabled {
-        join_set.spawn(EidolonHuntListener::run(state.clone()));
-    }
+struct Registration {
+    name: &'static str,
+    enabled: fn(&ListenerConfig) -> bool,
+    run: fn(State) -> ListenerFuture,
+}
 
-    if message_config.s_tier_arbitrations.enabled {
-        join_set.spawn(STierArbitrationListener::run(state.clone()));
-    }
+macro_rules! registration {
+    ($field:ident => $listener:ty) => {
+        registration!(stringify!($field), |cfg| cfg.$field.as_ref().enabled => $listener)
+    };
+    ($name:expr, $enabled:expr => $listener:ty) => {
+        Registration {
+            name: $name,
+            enabled: $enabled,
+            run: |state| Box::pin(<$listener>::run(state)),
+        }
+    };
+}
 
-    if message_config.meta_relics.enabled {
-        join_set.spawn(MetaRelicsListener::run(state.clone()));
-    }
+fn registrations() -> Vec<Registration> {
+    vec![
+        registration!(eidolon_hunts => EidolonHuntListener),
+        registration!(eidolon_hunts_warning => EidolonHuntWarningListener),
+        registration!(orb_vallis_warm => OrbVallisWarmListener),
+        registration!(cambion_drift => CambionDriftListener),
+        registration!(zariman => ZarimanListener),
+        registration!(arbitrations => ArbitrationListener),
+        registration!(arbitrations_warning => ArbitrationWarningListener),
+        registration!(meta_relics => MetaRelicsListener),
+        registration!(steel_path_disruption_fissures => SteelPathDisruptionFissuresListener),
+        registration!(void_storms => VoidStormListener),
+        registration!(
+            "fissure_rules",
+            |cfg| cfg.fissure_rules.iter().any(|rule| rule.options.enabled) => FissureRulesListener
+        ),
+        registration!(invasions => InvasionListener),
+        registration!(bounties => BountyListener),
+        registration!(
+            "persistent_enemies",
+            |cfg| cfg.persistent_enemies.enabled || cfg.persistent_enemies_defeated.enabled
+                => PersistentEnemyListener
+        ),
+        registration!(void_trader => VoidTraderListener),
+        registration!(nightwave => NightwaveListener),
+        registration!(duviri_circuit => DuviriCircuitListener),
+        registration!(
+            "steel_path",
+            |cfg| cfg.steel_path_reward.enabled || cfg.steel_path_incursions.enabled
+                => SteelPathListener
+        ),
+        registration!(daily_deals => DailyDealListener),
+        registration!(flash_sales => FlashSaleListener),
+        registration!(global_boosters => GlobalBoosterListener),
+        registration!(events => EventListener),
+        registration!(news => NewsListener),
+        registration!(sorties => SortieListener),
+        registration!(archon_hunts => ArchonHuntListener),
+    ]
+}
 
-    if message_config.steel_path_disruption_fissures.enabled {
-        join_set.spawn(SteelPathDisruptionFissuresListener::run(state.clone()));
-    }
+/// Runs every listener that is enabled in at least one channel.
+///
+/// Whenever the settings change, listeners that got enabled are started and listeners that got
+/// disabled everywhere are stopped.
+pub async fn run_listeners(state: State) -> anyhow::Result<()> {
+    let registrations = registrations();
 
-    Ok(())
+    let mut settings = state.settings.clone();
+    let mut join_set = JoinSet::new();
+    let mut running = HashMap::<&'static str, AbortHandle>::new();
+
+    loop {
+        settings.borrow_and_update();
+
+        for registration in &registrations {
+            let enabled = state.listener_enabled(registration.enabled);
+
+            match running.get(registration.name) {
+                None if enabled => {
+                    tracing::info!(listener = registration.name, "Starting listener");
+
+                    let handle = join_set.spawn((registration.run)(state.clone()));
+                    running.insert(registration.name, handle);
+                }
+                Some(handle) if !enabled => {
+                    tracing::info!(listener = registration.name, "Stopping listener");
+
+                    handle.abort();
+                    running.remove(registration.name);
+                }
+                _ => (),
+            }
+        }
+
+        loop {
+            tokio::select! {
+                changed = settings.changed() => {
+                    if changed.is_err() {
+                        // nothing can change anymore, just keep the listeners running
+                        while join_set.join_next().await.is_some() {}
+                        return Ok(());
+                    }
+
+                    break;
+                }
+                Some(res) = join_set.join_next_with_id(), if !join_set.is_empty() => {
+                    let id = match res {
+                        Ok((id, Ok(()))) => {
+                            tracing::info!("Listener completed successfully");
+                            id
+                        }
+                        Ok((id, Err(error))) => {
+                            tracing::error!(?error, "Listener failed");
+                            id
+                        }
+                        Err(error) if error.is_cancelled() => continue,
+                        Err(error) => {
+                            tracing::error!(?error, "Listener panicked");
+                            error.id()
+                        }
+                    };
+
+                    running.retain(|_, handle| handle.id() != id);
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,361 @@
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use chrono::{
+    DateTime,
+    NaiveTime,
+    Utc,
+};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use twitch_irc::login::{
+    LoginCredentials,
+    RefreshingLoginCredentials,
+};
+
+use crate::{
+    listener::{
+        config::{
+            QuietHours,
+            ScheduleConfig,
+        },
+        placeholders,
+    },
+    outgoing::MAX_MESSAGE_LENGTH,
+    placeholder::{
+        ChannelName,
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+    token_storage::SimpleTokenStorage,
+};
+
+const STREAMS_URL: &str = "https://api.twitch.tv/helix/streams";
+
+/// How long a channel's live status is reused before asking Twitch again
+const LIVE_STATUS_TTL: Duration = Duration::from_secs(60);
+
+const SUMMARY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How many suppressed notifications are kept per channel. A summary is a single chat message,
+/// so more wouldn't fit anyway. The rest are only counted.
+const MAX_SUPPRESSED: usize = 64;
+
+impl QuietHours {
+    /// Windows ending before they start span midnight
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl ScheduleConfig {
+    pub fn is_quiet(&self, now: DateTime<Utc>) -> bool {
+        let time = now.with_timezone(&self.time_zone).time();
+
+        self.quiet_hours.iter().any(|window| window.contains(time))
+    }
+}
+
+/// The notifications a channel didn't get
+#[derive(Debug, Default)]
+struct Suppressed {
+    messages: Vec<String>,
+    /// Notifications that didn't fit into `messages`
+    overflow: usize,
+}
+
+impl Suppressed {
+    fn push(&mut self, message: String) {
+        if self.messages.len() < MAX_SUPPRESSED {
+            self.messages.push(message);
+        } else {
+            self.overflow += 1;
+        }
+    }
+
+    fn count(&self) -> usize {
+        self.messages.len() + self.overflow
+    }
+}
+
+#[derive(Deserialize)]
+struct Streams {
+    data: Vec<serde_json::Value>,
+}
+
+/// Decides whether a channel may get notifications right now and keeps the ones it didn't get
+pub struct Scheduler {
+    http: reqwest::Client,
+    client_id: String,
+    login: RefreshingLoginCredentials<SimpleTokenStorage>,
+    live: Mutex<HashMap<String, (Instant, bool)>>,
+    suppressed: Mutex<HashMap<String, Suppressed>>,
+}
+
+impl Scheduler {
+    pub fn new(client_id: String, login: RefreshingLoginCredentials<SimpleTokenStorage>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            client_id,
+            login,
+            live: Mutex::default(),
+            suppressed: Mutex::default(),
+        }
+    }
+
+    /// Whether `channel` may get notifications right now
+    pub async fn allows(&self, channel: &str, schedule: &ScheduleConfig) -> bool {
+        !schedule.is_quiet(Utc::now()) && (!schedule.only_while_live || self.is_live(channel).await)
+    }
+
+    /// Keeps `message` for the summary of `channel`, if the channel wants one
+    pub async fn suppress(&self, channel: &str, schedule: &ScheduleConfig, message: String) {
+        if schedule.summarize_suppressed {
+            self.suppressed
+                .lock()
+                .await
+                .entry(channel.to_owned())
+                .or_default()
+                .push(message);
+        }
+    }
+
+    async fn is_live(&self, channel: &str) -> bool {
+        if let Some((checked_at, live)) = self.live.lock().await.get(channel)
+            && checked_at.elapsed() < LIVE_STATUS_TTL
+        {
+            return *live;
+        }
+
+        let live = match self.fetch_live(channel).await {
+            Ok(live) => live,
+            Err(error) => {
+                // rather send too much than nothing at all
+                tracing::warn!(channel, "Failed to check if the channel is live: {error:#}");
+                true
+            }
+        };
+
+        self.live
+            .lock()
+            .await
+            .insert(channel.to_owned(), (Instant::now(), live));
+
+        live
+    }
+
+    async fn fetch_live(&self, channel: &str) -> anyhow::Result<bool> {
+        let token = self
+            .login
+            .get_credentials()
+            .await?
+            .token
+            .unwrap_or_default();
+
+        let streams = self
+            .http
+            .get(STREAMS_URL)
+            .query(&[("user_login", channel)])
+            .header("Client-Id", &self.client_id)
+            .bearer_auth(token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Streams>()
+            .await?;
+
+        Ok(!streams.data.is_empty())
+    }
+}
+
+/// Sends every channel a summary of its suppressed notifications once it may get notifications
+/// again
+pub async fn send_suppressed_summaries(state: State) -> anyhow::Result<()> {
+    let mut interval = tokio::time::interval(SUMMARY_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let settings = state.settings();
+
+        for channel_name in state.channels.iter() {
+            let schedule = &settings.listener_cfg(&channel_name.0).schedule;
+
+            if !state.scheduler.allows(&channel_name.0, schedule).await {
+                continue;
+            }
+
+            let Some(suppressed) = state
+                .scheduler
+                .suppressed
+                .lock()
+                .await
+                .remove(&*channel_name.0)
+            else {
+                continue;
+            };
+
+            let summary = summarize(&schedule.summary_format, channel_name, &suppressed);
+
+            if let Err(error) = state.messages.send(channel_name.to_string(), summary).await {
+                tracing::error!(channel = %channel_name, "Failed to send a summary: {error:#}");
+            }
+        }
+    }
+}
+
+/// Fills in `format` with as many of the suppressed notifications as fit into a single chat
+/// message. The ones that don't fit are counted as `+N more`.
+fn summarize(format: &str, channel_name: &ChannelName, suppressed: &Suppressed) -> String {
+    let total = suppressed.count();
+    let count = placeholders::Count(total.to_string());
+
+    let fill = |notifications: String| {
+        apply_placeholders(
+            format,
+            [
+                &count as &dyn Placeholder,
+                &placeholders::Notifications(notifications),
+                channel_name,
+            ],
+        )
+    };
+
+    let mut summary = fill(format!("+{total} more"));
+    let mut shown = String::new();
+
+    // adds one notification at a time until the next one doesn't fit anymore
+    for (index, message) in suppressed.messages.iter().enumerate() {
+        if !shown.is_empty() {
+            shown.push_str(" | ");
+        }
+        shown.push_str(message);
+
+        let more = total - (index + 1);
+        let candidate = if more > 0 {
+            fill(format!("{shown} | +{more} more"))
+        } else {
+            fill(shown.clone())
+        };
+
+        if candidate.chars().count() > MAX_MESSAGE_LENGTH {
+            break;
+        }
+
+        summary = candidate;
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{
+        NaiveTime,
+        TimeZone,
+        Utc,
+    };
+
+    use crate::{
+        listener::{
+            config::{
+                QuietHours,
+                ScheduleConfig,
+            },
+            schedule::{
+                MAX_SUPPRESSED,
+                Suppressed,
+                summarize,
+            },
+        },
+        outgoing::MAX_MESSAGE_LENGTH,
+        placeholder::ChannelName,
+    };
+
+    #[test]
+    fn test_quiet_hours_span_midnight() {
+        let time = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+
+        let night = QuietHours {
+            start: time(22),
+            end: time(8),
+        };
+
+        assert!(night.contains(time(23)));
+        assert!(night.contains(time(3)));
+        assert!(!night.contains(time(8)));
+        assert!(!night.contains(time(12)));
+
+        let afternoon = QuietHours {
+            start: time(12),
+            end: time(14),
+        };
+
+        assert!(afternoon.contains(time(13)));
+        assert!(!afternoon.contains(time(22)));
+    }
+
+    #[test]
+    fn test_quiet_hours_follow_daylight_saving_time() {
+        let schedule = ScheduleConfig {
+            time_zone: chrono_tz::Europe::Berlin,
+            quiet_hours: vec![QuietHours {
+                start: NaiveTime::from_hms_opt(23, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            }],
+            ..Default::default()
+        };
+
+        // 07:30 UTC is 08:30 in winter and 09:30 in summer
+        assert!(schedule.is_quiet(Utc.with_ymd_and_hms(2025, 1, 15, 7, 30, 0).unwrap()));
+        assert!(!schedule.is_quiet(Utc.with_ymd_and_hms(2025, 7, 15, 7, 30, 0).unwrap()));
+    }
+
+    fn suppressed(messages: impl IntoIterator<Item = String>) -> Suppressed {
+        let mut suppressed = Suppressed::default();
+        messages
+            .into_iter()
+            .for_each(|message| suppressed.push(message));
+        suppressed
+    }
+
+    #[test]
+    fn test_summary_fits_into_a_message() {
+        let channel = ChannelName::from("channel".to_owned());
+        let format = "{count}: {notifications}";
+
+        let few = suppressed(["a".to_owned(), "b".to_owned()]);
+        assert_eq!(summarize(format, &channel, &few), "2: a | b");
+
+        let many = suppressed(vec!["x".repeat(100); 10]);
+        let summary = summarize(format, &channel, &many);
+
+        assert!(summary.chars().count() <= MAX_MESSAGE_LENGTH);
+        assert!(summary.starts_with("10: "));
+        assert!(summary.ends_with(" | +6 more"));
+    }
+
+    #[test]
+    fn test_suppressed_notifications_are_capped() {
+        let channel = ChannelName::from("channel".to_owned());
+
+        let flood = suppressed((0..MAX_SUPPRESSED + 100).map(|i| i.to_string()));
+        assert_eq!(flood.messages.len(), MAX_SUPPRESSED);
+        assert_eq!(flood.overflow, 100);
+
+        let summary = summarize("{count}: {notifications}", &channel, &flood);
+
+        assert!(summary.starts_with(&format!("{}: 0 | 1 | ", MAX_SUPPRESSED + 100)));
+        assert!(summary.ends_with(" | +100 more"));
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::Error;
+use warframe::worldstate::queryable::Sortie;
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders::{
+            self,
+            Stage,
+            StagedMission,
+        },
+    },
+    state::State,
+};
+
+pub struct SortieListener;
+
+impl Listener for SortieListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_update_with_state::<_, Sortie, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, before: &Sortie, sortie: &Sortie) {
+    if before.id == sortie.id {
+        return;
+    }
+
+    let stages = sortie
+        .missions
+        .iter()
+        .map(|mission| Stage {
+            mission_type: &mission.mission_type,
+            node: &mission.node,
+            modifier: Some(&mission.modifier),
+        })
+        .collect::<Vec<_>>();
+
+    let mission = StagedMission::new(
+        &sortie.boss,
+        placeholders::faction_name(&sortie.faction),
+        &stages,
+    );
+
+    state
+        .send_listener_response(
+            &format!("sorties:{}", sortie.id),
+            |cfg| &cfg.sorties,
+            mission.placeholders(),
+        )
+        .await;
+}
@@ -0,0 +1,82 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::Deserialize;
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+        polling::Poller,
+    },
+    placeholder::Placeholder,
+    state::State,
+};
+
+/// The Steel Path's weekly Teshin rotation and daily incursions. Not covered by the worldstate
+/// client.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SteelPath {
+    pub expiry: DateTime<Utc>,
+    pub current_reward: SteelPathReward,
+    pub incursions: Incursions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct SteelPathReward {
+    pub name: String,
+    pub cost: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Incursions {
+    pub id: String,
+    pub expiry: DateTime<Utc>,
+}
+
+/// Both the weekly reward and the daily incursions, they come from the same endpoint
+pub struct SteelPathListener;
+
+impl Listener for SteelPathListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut poller = Poller::<SteelPath>::new("steelPath");
+
+        loop {
+            let (before, steel_path) = poller.next_update().await;
+
+            if before.current_reward != steel_path.current_reward {
+                let reward = &steel_path.current_reward;
+
+                state
+                    .send_listener_response(
+                        &format!("steel_path_reward:{}", steel_path.expiry.timestamp()),
+                        |cfg| &cfg.steel_path_reward,
+                        [
+                            &placeholders::Reward(format!(
+                                "{} ({} Steel Essence)",
+                                reward.name, reward.cost
+                            )) as &dyn Placeholder,
+                            &placeholders::Reset::until(steel_path.expiry),
+                        ],
+                    )
+                    .await;
+            }
+
+            if before.incursions.id != steel_path.incursions.id {
+                state
+                    .send_listener_response(
+                        &format!("steel_path_incursions:{}", steel_path.incursions.id),
+                        |cfg| &cfg.steel_path_incursions,
+                        [
+                            &placeholders::Reward(steel_path.current_reward.name.clone())
+                                as &dyn Placeholder,
+                            &placeholders::Reset::until(steel_path.incursions.expiry),
+                        ],
+                    )
+                    .await;
+            }
+        }
+    }
+}
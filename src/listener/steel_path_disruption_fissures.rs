@@ -28,17 +28,33 @@ impl Listener for SteelPathDisruptionFissuresListener {
 }
 
 async fn callback(state: State, fissure: &Fissure, change: Change) {
-    if change == Change::Added
-        && fissure.tier != Tier::Requiem
-        && fissure.mission_key == MissionType::Disruption
-        && fissure.is_hard
+    if fissure.tier == Tier::Requiem
+        || fissure.mission_key != MissionType::Disruption
+        || !fissure.is_hard
     {
-        state
-            .send_listener_response(
-                &state.listener_cfg().steel_path_disruption_fissures.format,
-                [&placeholders::Node(&fissure.node) as &dyn Placeholder],
-            )
-            .await
-            .unwrap();
+        return;
+    }
+
+    let placeholders = [&placeholders::Node(&fissure.node) as &dyn Placeholder];
+
+    match change {
+        Change::Added => {
+            state
+                .send_listener_response(
+                    &format!("steel_path_disruption_fissures:{}", fissure.id),
+                    |cfg| &cfg.steel_path_disruption_fissures.options,
+                    placeholders,
+                )
+                .await
+        }
+        Change::Removed => {
+            state
+                .send_listener_end(
+                    &format!("steel_path_disruption_fissures_end:{}", fissure.id),
+                    |cfg| Some(&cfg.steel_path_disruption_fissures),
+                    placeholders,
+                )
+                .await
+        }
     }
 }
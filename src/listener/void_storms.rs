@@ -0,0 +1,67 @@
+use anyhow::Error;
+use warframe::worldstate::{
+    Change,
+    TimedEvent,
+    queryable::Fissure,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+pub struct VoidStormListener;
+
+impl Listener for VoidStormListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_nested_update_with_state::<_, Fissure, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, fissure: &Fissure, change: Change) {
+    if !fissure.is_storm {
+        return;
+    }
+
+    let node = placeholders::Node(&fissure.node);
+    let tier_placeholder = placeholders::Tier(&fissure.tier_name);
+    let expiry = placeholders::Expiry::until(fissure.expiry());
+
+    let placeholders = [&node as &dyn Placeholder, &tier_placeholder, &expiry];
+
+    match change {
+        Change::Added => {
+            state
+                .send_listener_message(&format!("void_storms:{}", fissure.id), |cfg| {
+                    let config = &cfg.void_storms;
+
+                    (config.options.options.enabled && config.watches(&fissure.tier))
+                        .then(|| apply_placeholders(&config.options.options.format, placeholders))
+                })
+                .await
+        }
+        Change::Removed => {
+            state
+                .send_listener_end(
+                    &format!("void_storms_end:{}", fissure.id),
+                    |cfg| {
+                        let config = &cfg.void_storms;
+                        config.watches(&fissure.tier).then_some(&config.options)
+                    },
+                    placeholders,
+                )
+                .await
+        }
+    }
+}
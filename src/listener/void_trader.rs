@@ -0,0 +1,84 @@
+use anyhow::Error;
+use warframe::worldstate::{
+    TimedEvent,
+    queryable::VoidTrader,
+};
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+pub struct VoidTraderListener;
+
+impl Listener for VoidTraderListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        state
+            .wf
+            .call_on_update_with_state::<_, VoidTrader, _>(callback, state.clone())
+            .await
+            .map_err(Error::from)
+    }
+}
+
+async fn callback(state: State, before: &VoidTrader, trader: &VoidTrader) {
+    // the inventory is only filled while Baro is at a relay
+    if !before.inventory.is_empty() || trader.inventory.is_empty() {
+        return;
+    }
+
+    let relay = placeholders::Relay(&trader.location);
+    let departure = placeholders::Departure::until(trader.expiry());
+
+    state
+        .send_listener_message(
+            &format!("void_trader:{}", trader.expiry().timestamp()),
+            |cfg| {
+                let config = &cfg.void_trader;
+
+                config.options.enabled.then(|| {
+                    let inventory = placeholders::Inventory(notable_inventory(
+                        trader.inventory.iter().map(|item| item.item.as_ref()),
+                        &config.notable_items,
+                    ));
+
+                    apply_placeholders(
+                        &config.options.format,
+                        [&relay as &dyn Placeholder, &departure, &inventory],
+                    )
+                })
+            },
+        )
+        .await;
+}
+
+fn notable_inventory<'a>(
+    inventory: impl Iterator<Item = &'a str>,
+    notable_items: &[String],
+) -> String {
+    let notable_items = notable_items
+        .iter()
+        .map(|item| item.to_lowercase())
+        .collect::<Vec<_>>();
+
+    let inventory = inventory
+        .filter(|item| {
+            let item = item.to_lowercase();
+
+            notable_items.is_empty() || notable_items.iter().any(|notable| item.contains(notable))
+        })
+        .collect::<Vec<_>>();
+
+    if inventory.is_empty() {
+        "nothing notable".to_owned()
+    } else {
+        inventory.join(", ")
+    }
+}
@@ -0,0 +1,92 @@
+use std::iter;
+
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+
+use crate::{
+    listener::{
+        config::{
+            ListenerConfig,
+            WarningConfig,
+        },
+        placeholders::TimeLeft,
+        sleep_unless_reloaded,
+    },
+    placeholder::{
+        Placeholder,
+        apply_placeholders,
+    },
+    state::State,
+};
+
+/// Sends the warning selected by `select` to every channel, each at the lead time the channel
+/// configured before `starts_at`. Channels `select` returns `None` for are skipped.
+/// `{time_left}` is filled in when the warning is sent.
+///
+/// Warnings whose lead time already passed are sent right away, as long as the event didn't start
+/// yet. `event` identifies the event, see [`State::send_listener_message`].
+///
+/// Changes to the lead times are picked up while waiting.
+pub async fn send_warnings(
+    state: &State,
+    event: &str,
+    starts_at: DateTime<Utc>,
+    select: impl Fn(&ListenerConfig) -> Option<&WarningConfig>,
+    placeholders: &[&dyn Placeholder],
+) {
+    let mut settings = state.settings.clone();
+
+    // the lead times are recomputed whenever the settings change. Warnings that were already sent
+    // aren't sent again, see `State::send_listener_message`
+    'reload: loop {
+        let current = settings.borrow_and_update().clone();
+
+        let mut lead_times = state
+            .channels
+            .iter()
+            .filter_map(|channel| select(current.listener_cfg(&channel.0)))
+            .filter(|warning| warning.options.enabled)
+            .map(|warning| warning.minutes_before)
+            .collect::<Vec<_>>();
+
+        // the longest lead time is the earliest warning
+        lead_times.sort_unstable_by(|a, b| b.cmp(a));
+        lead_times.dedup();
+
+        for minutes_before in lead_times {
+            let warn_at = starts_at - TimeDelta::minutes(minutes_before.into());
+
+            if let Ok(time_to_sleep) = (warn_at - Utc::now()).to_std() {
+                if !sleep_unless_reloaded(&mut settings, time_to_sleep).await {
+                    continue 'reload;
+                }
+            } else if starts_at <= Utc::now() {
+                continue;
+            }
+
+            let time_left = TimeLeft::until(starts_at);
+
+            state
+                .send_listener_message(&format!("{event}:{minutes_before}"), |cfg| {
+                    let warning = select(cfg)?;
+
+                    (warning.options.enabled && warning.minutes_before == minutes_before).then(
+                        || {
+                            apply_placeholders(
+                                &warning.options.format,
+                                placeholders
+                                    .iter()
+                                    .chain(iter::once(&(&time_left as &dyn Placeholder))),
+                            )
+                        },
+                    )
+                })
+                .await;
+        }
+
+        break;
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{
+    DateTime,
+    Utc,
+};
+use serde::Deserialize;
+
+use crate::{
+    listener::{
+        Listener,
+        placeholders,
+        polling::Poller,
+    },
+    placeholder::Placeholder,
+    state::State,
+};
+
+/// The Zariman's faction rotation. Not covered by the worldstate client.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZarimanCycle {
+    pub expiry: DateTime<Utc>,
+    pub is_corpus: bool,
+}
+
+pub struct ZarimanListener;
+
+impl Listener for ZarimanListener {
+    async fn run(state: State) -> anyhow::Result<()> {
+        let mut poller = Poller::<ZarimanCycle>::new("zarimanCycle");
+
+        loop {
+            let (before, zariman) = poller.next_update().await;
+            callback(&state, &before, &zariman).await;
+        }
+    }
+}
+
+async fn callback(state: &State, before: &ZarimanCycle, zariman: &ZarimanCycle) {
+    if before.is_corpus == zariman.is_corpus {
+        return;
+    }
+
+    let faction = if zariman.is_corpus {
+        "Corpus"
+    } else {
+        "Grineer"
+    };
+
+    state
+        .send_listener_response(
+            &format!("zariman:{}", zariman.expiry.timestamp()),
+            |cfg| &cfg.zariman,
+            [
+                &placeholders::Faction(faction) as &dyn Placeholder,
+                &placeholders::TimeLeft::until(zariman.expiry),
+            ],
+        )
+        .await;
+}
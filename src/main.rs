@@ -4,7 +4,10 @@ pub mod commands;
 pub mod config;
 pub mod credentials;
 pub mod listener;
+pub mod oauth;
+pub mod outgoing;
 pub mod placeholder;
+pub mod sent_notifications;
 pub mod state;
 pub mod token_storage;
 
@@ -22,6 +25,7 @@ use clap::Parser;
 use regex::Regex;
 use tokio::{
     fs,
+    sync::watch,
     task::JoinSet,
 };
 use tracing_subscriber::filter::LevelFilter;
@@ -42,9 +46,15 @@ use crate::{
         Cli,
         Commands,
     },
-    config::Config,
+    config::{
+        Config,
+        Settings,
+    },
     credentials::ComposedCredentials,
+    listener::schedule::Scheduler,
+    outgoing::MessageQueue,
     placeholder::ChannelName,
+    sent_notifications::SentNotifications,
     state::State,
     token_storage::{
         CREDENTIALS_PATH,
@@ -73,7 +83,14 @@ pub async fn main() -> anyhow::Result<()> {
 
     match cli.command {
         Commands::Init { id, secret } => init(id, secret).await?,
-        Commands::Run { channel_name } => run(channel_name).await?,
+        Commands::Login {
+            id,
+            secret,
+            device,
+            device_url,
+            token_url,
+        } => login(id, secret, device, device_url, token_url).await?,
+        Commands::Run { channel_names } => run(channel_names).await?,
     }
 
     Ok(())
@@ -124,69 +141,130 @@ async fn init(id: String, secret: String) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn run(channel_name: String) -> anyhow::Result<()> {
+async fn login(
+    id: String,
+    secret: String,
+    device: bool,
+    device_url: String,
+    token_url: String,
+) -> anyhow::Result<()> {
+    Config::load()?;
+
+    let token = if device {
+        oauth::device_code::login(&id, &device_url, &token_url).await?
+    } else {
+        oauth::authorization_code::login(&id, &secret, &token_url).await?
+    };
+
+    oauth::store_credentials(id, secret, token).await?;
+
+    tracing::info!("Logged in successfully, credentials saved to {CREDENTIALS_PATH}");
+
+    Ok(())
+}
+
+async fn run(channel_names: Vec<String>) -> anyhow::Result<()> {
     let arbi_data = load_arbi_data()?;
     let credentials = load_credentials().await?;
     let notifier_config = Config::load()?;
 
+    let mut channel_names = channel_names
+        .into_iter()
+        .chain(notifier_config.channels.keys().cloned())
+        .map(|channel_name| channel_name.to_lowercase())
+        .collect::<Vec<_>>();
+    channel_names.sort();
+    channel_names.dedup();
+
+    if channel_names.is_empty() {
+        bail!(
+            "No channels to join. Pass them to the run command or add them to the config (`wf_twitch_notifier run -h` for more info)"
+        );
+    }
+
     let mut join_set = JoinSet::new();
 
     let wf = warframe::worldstate::Client::new();
 
+    let login = RefreshingLoginCredentials::<SimpleTokenStorage>::init(
+        credentials.client_id.clone(),
+        credentials.client_secret.clone(),
+        SimpleTokenStorage(match tokio::fs::read_to_string(CREDENTIALS_PATH).await {
+            Ok(contents) => {
+                let token: ComposedCredentials = serde_json::from_str(&contents)?;
+                token
+            }
+            Err(_) => bail!(
+                "Failed to read {}. Please use the init command (`wf_twitch_notifier init -h` for more info)",
+                CREDENTIALS_PATH
+            ),
+        }),
+    );
+
     let (incoming_messages, client) = TwitchIRCClient::<
         SecureTCPTransport,
         RefreshingLoginCredentials<SimpleTokenStorage>,
-    >::new(ClientConfig::new_simple(
-        RefreshingLoginCredentials::<SimpleTokenStorage>::init(
-            credentials.client_id.clone(),
-            credentials.client_secret.clone(),
-            SimpleTokenStorage(match tokio::fs::read_to_string(CREDENTIALS_PATH).await {
-                Ok(contents) => {
-                    let token: ComposedCredentials = serde_json::from_str(&contents)?;
-                    token
-                }
-                Err(_) => bail!(
-                    "Failed to read {}. Please use the init command (`wf_twitch_notifier init -h` for more info)",
-                    CREDENTIALS_PATH
-                ),
-            }),
-        ),
-    ));
+    >::new(ClientConfig::new_simple(login.clone()));
 
-    client.join(channel_name.clone()).unwrap();
+    let (messages, message_worker) = MessageQueue::new(client.clone());
+    join_set.spawn(message_worker.run());
 
-    client
-        .say(
-            channel_name.clone(),
-            format!("Hello @{channel_name}, I'm running the setup!"),
-        )
-        .await
-        .unwrap();
+    for channel_name in &channel_names {
+        client.join(channel_name.clone()).unwrap();
+
+        messages
+            .send(
+                channel_name.clone(),
+                format!("Hello @{channel_name}, I'm running the setup!"),
+            )
+            .await
+            .unwrap();
+    }
+
+    let channels: Arc<[ChannelName]> = channel_names
+        .iter()
+        .cloned()
+        .map(ChannelName::from)
+        .collect();
+
+    let (settings_sender, settings) =
+        watch::channel(Arc::new(Settings::new(notifier_config, &channels)?));
+
+    let scheduler = Arc::new(Scheduler::new(credentials.client_id.clone(), login));
 
     let state = State {
         client: client.clone(),
-        config: Arc::new(notifier_config),
+        messages: messages.clone(),
+        settings,
         credentials: Arc::new(credentials),
         arbi_data: Arc::new(arbi_data),
-        channel_name: ChannelName::from(channel_name.clone()),
+        channels: channels.clone(),
         wf,
         wfm: Arc::new(market::Client::new()),
+        sent: Arc::new(SentNotifications::load().await),
+        scheduler,
     };
 
-    listener::register_listeners(&mut join_set, state.clone())?;
+    join_set.spawn(config::reload::watch_config(settings_sender, channels));
+
+    join_set.spawn(listener::run_listeners(state.clone()));
+
+    join_set.spawn(listener::schedule::send_suppressed_summaries(state.clone()));
 
     join_set.spawn(commands::listen_to_commands(
         incoming_messages,
         state.clone(),
     ));
 
-    client
-        .say(
-            channel_name.clone(),
-            format!("@{channel_name}, setup successful!"),
-        )
-        .await
-        .unwrap();
+    for channel_name in &channel_names {
+        messages
+            .send(
+                channel_name.clone(),
+                format!("@{channel_name}, setup successful!"),
+            )
+            .await
+            .unwrap();
+    }
 
     while let Some(res) = join_set.join_next().await {
         match res {
@@ -0,0 +1,358 @@
+use std::{
+    process::Command,
+    time::Duration,
+};
+
+use anyhow::{
+    Context,
+    bail,
+};
+use reqwest::Url;
+use tokio::{
+    io::{
+        AsyncBufReadExt,
+        AsyncWriteExt,
+        BufReader,
+    },
+    net::{
+        TcpListener,
+        TcpStream,
+    },
+    task::JoinSet,
+    time,
+};
+use twitch_irc::login::UserAccessToken;
+
+use crate::oauth::{
+    AUTHORIZE_URL,
+    REDIRECT_URL,
+    SCOPES,
+    TokenResponse,
+};
+
+/// How long to wait for the user to authorize the app
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// How long a connection may take to send its request. Browsers open connections in advance
+/// that might never send one.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs the OAuth authorization code flow.
+///
+/// Opens the authorize page in the browser, waits for Twitch to redirect back to
+/// [`REDIRECT_URL`] and exchanges the received code at `token_url`.
+pub async fn login(
+    client_id: &str,
+    client_secret: &str,
+    token_url: &str,
+) -> anyhow::Result<UserAccessToken> {
+    let csrf_state = random_state();
+
+    let authorize_url = Url::parse_with_params(
+        AUTHORIZE_URL,
+        [
+            ("response_type", "code"),
+            ("client_id", client_id),
+            ("redirect_uri", REDIRECT_URL),
+            ("scope", SCOPES),
+            ("state", &csrf_state),
+        ],
+    )?;
+
+    // listen on the host Twitch redirects the browser to, `localhost` isn't always 127.0.0.1
+    let redirect_url = Url::parse(REDIRECT_URL)?;
+    let host = redirect_url
+        .host_str()
+        .context("REDIRECT_URL has no host")?;
+    let port = redirect_url
+        .port_or_known_default()
+        .context("REDIRECT_URL has no port")?;
+
+    let listener = TcpListener::bind((host, port))
+        .await
+        .context(format!("Failed to listen on {host}:{port}"))?;
+
+    tracing::info!("Please authorize the app in your browser: {authorize_url}");
+
+    if let Err(error) = open_browser(authorize_url.as_str()) {
+        tracing::warn!(
+            ?error,
+            "Failed to open the browser, please open the link manually"
+        );
+    }
+
+    complete_login(&listener, &csrf_state, client_id, client_secret, token_url).await
+}
+
+/// Waits for the redirect on `listener` and exchanges its code at `token_url`
+async fn complete_login(
+    listener: &TcpListener,
+    csrf_state: &str,
+    client_id: &str,
+    client_secret: &str,
+    token_url: &str,
+) -> anyhow::Result<UserAccessToken> {
+    let code = time::timeout(LOGIN_TIMEOUT, receive_code(listener, csrf_state))
+        .await
+        .context("Timed out waiting for the authorization")??;
+
+    let response = reqwest::Client::new()
+        .post(token_url)
+        .form(&[
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("code", &code),
+            ("grant_type", "authorization_code"),
+            ("redirect_uri", REDIRECT_URL),
+        ])
+        .send()
+        .await
+        .context("Request to the token endpoint failed")?
+        .error_for_status()
+        .context("Failed to exchange the authorization code")?
+        .json::<TokenResponse>()
+        .await
+        .context("Deserializing the token response failed")?;
+
+    Ok(response.into())
+}
+
+/// What Twitch redirected the browser back with
+enum Redirect {
+    Code(String),
+    Denied(String),
+}
+
+/// Accepts connections until Twitch redirects the browser back to us, then returns the code.
+async fn receive_code(listener: &TcpListener, csrf_state: &str) -> anyhow::Result<String> {
+    // connections are handled concurrently so a stalled one can't hold up the redirect
+    let mut connections = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                let csrf_state = csrf_state.to_owned();
+
+                connections.spawn(async move { handle_connection(stream, &csrf_state).await });
+            }
+            Some(handled) = connections.join_next() => match handled? {
+                Ok(Some(Redirect::Code(code))) => return Ok(code),
+                Ok(Some(Redirect::Denied(reason))) => bail!("Authorization failed: {reason}"),
+                Ok(None) => (),
+                Err(error) => tracing::debug!(?error, "Failed to handle a connection"),
+            },
+        }
+    }
+}
+
+/// Answers a single request, returning the redirect if it was one
+async fn handle_connection(
+    mut stream: TcpStream,
+    csrf_state: &str,
+) -> anyhow::Result<Option<Redirect>> {
+    let Some(target) = time::timeout(REQUEST_TIMEOUT, read_request_target(&mut stream))
+        .await
+        .context("Timed out reading the request")??
+    else {
+        return Ok(None);
+    };
+
+    let url = Url::parse(REDIRECT_URL)?.join(&target)?;
+    let query = |key: &str| {
+        url.query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    let state_matches = query("state").as_deref() == Some(csrf_state);
+
+    if let Some(error) = query("error") {
+        if !state_matches {
+            respond(&mut stream, "400 Bad Request", "State mismatch.").await?;
+            return Ok(None);
+        }
+
+        respond(&mut stream, "400 Bad Request", "Authorization failed.").await?;
+
+        return Ok(Some(Redirect::Denied(
+            query("error_description").unwrap_or(error),
+        )));
+    }
+
+    // browsers also request things like the favicon
+    let Some(code) = query("code") else {
+        respond(&mut stream, "404 Not Found", "Not found.").await?;
+        return Ok(None);
+    };
+
+    // not from the login we started, keep waiting for the real redirect
+    if !state_matches {
+        tracing::warn!(
+            "Ignoring a redirect with a state that doesn't match the one sent to Twitch"
+        );
+        respond(&mut stream, "400 Bad Request", "State mismatch.").await?;
+        return Ok(None);
+    }
+
+    respond(
+        &mut stream,
+        "200 OK",
+        "Login successful! You can close this tab now.",
+    )
+    .await?;
+
+    Ok(Some(Redirect::Code(code)))
+}
+
+/// Reads an HTTP request and returns its target, e.g. `/?code=...`
+async fn read_request_target(stream: &mut TcpStream) -> anyhow::Result<Option<String>> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+
+    // skip the headers, we don't need them
+    let mut header = String::new();
+    while reader.read_line(&mut header).await? > 0 && !header.trim_end().is_empty() {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+
+    Ok(match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Some(target.to_owned()),
+        _ => None,
+    })
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> anyhow::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+
+    Ok(())
+}
+
+/// An unguessable value to protect the redirect against CSRF
+fn random_state() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+fn open_browser(url: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+
+    command.arg(url).spawn()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    /// Sends a redirect to `address` like a browser would and returns the response's status line
+    async fn redirect(address: std::net::SocketAddr, query: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(format!("GET /?{query} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        response.lines().next().unwrap_or_default().to_owned()
+    }
+
+    /// Answers a single token request with a token and returns the request's form body
+    async fn serve_token(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut content_length = 0;
+        let mut line = String::new();
+        while reader.read_line(&mut line).await.unwrap() > 0 && !line.trim_end().is_empty() {
+            if let Some((name, value)) = line.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse().unwrap();
+            }
+            line.clear();
+        }
+
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await.unwrap();
+
+        let token = r#"{"access_token":"access","refresh_token":"refresh","expires_in":3600}"#;
+        reader
+            .get_mut()
+            .write_all(
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{token}",
+                    token.len()
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+
+        String::from_utf8(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_login_exchanges_the_redirected_code() {
+        let redirect_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let redirect_address = redirect_listener.local_addr().unwrap();
+
+        let token_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let token_url = format!("http://{}/token", token_listener.local_addr().unwrap());
+        let token_server = tokio::spawn(serve_token(token_listener));
+
+        let login = tokio::spawn(async move {
+            complete_login(
+                &redirect_listener,
+                "expected_state",
+                "client_id",
+                "client_secret",
+                &token_url,
+            )
+            .await
+        });
+
+        // a redirect with the wrong state is rejected and the login keeps waiting
+        assert_eq!(
+            redirect(redirect_address, "code=forged&state=other_state").await,
+            "HTTP/1.1 400 Bad Request"
+        );
+        assert!(!login.is_finished());
+
+        assert_eq!(
+            redirect(redirect_address, "code=the_code&state=expected_state").await,
+            "HTTP/1.1 200 OK"
+        );
+
+        let token = login.await.unwrap().unwrap();
+        assert_eq!(token.access_token, "access");
+        assert_eq!(token.refresh_token, "refresh");
+
+        let body = token_server.await.unwrap();
+        assert!(body.contains("code=the_code"));
+        assert!(body.contains("grant_type=authorization_code"));
+        assert!(!body.contains("forged"));
+    }
+}
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use anyhow::{
+    Context,
+    bail,
+};
+use serde::Deserialize;
+use tokio::time::Instant;
+use twitch_irc::login::UserAccessToken;
+
+use crate::oauth::{
+    SCOPES,
+    TokenResponse,
+};
+
+const GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+#[derive(Debug, Clone, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    /// Seconds until the device code expires
+    expires_in: u64,
+    /// Seconds to wait between polling the token endpoint
+    interval: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+/// Runs the OAuth device authorization grant flow.
+///
+/// Prints a link and a code the user has to enter on any device with a browser, then polls
+/// `token_url` until the login is approved.
+pub async fn login(
+    client_id: &str,
+    device_url: &str,
+    token_url: &str,
+) -> anyhow::Result<UserAccessToken> {
+    let client = reqwest::Client::new();
+
+    let device = client
+        .post(device_url)
+        .form(&[("client_id", client_id), ("scopes", SCOPES)])
+        .send()
+        .await
+        .context("Request to the device endpoint failed")?
+        .error_for_status()
+        .context("Failed to request a device code")?
+        .json::<DeviceCodeResponse>()
+        .await
+        .context("Deserializing the device code response failed")?;
+
+    tracing::info!(
+        "To log in, open {} on any device and enter the code {}",
+        device.verification_uri,
+        device.user_code
+    );
+
+    let expires_at = Instant::now() + Duration::from_secs(device.expires_in);
+    let mut interval = Duration::from_secs(device.interval);
+
+    loop {
+        tokio::time::sleep(interval).await;
+
+        if Instant::now() >= expires_at {
+            bail!("The device code expired before the login was approved");
+        }
+
+        let response = client
+            .post(token_url)
+            .form(&[
+                ("client_id", client_id),
+                ("scopes", SCOPES),
+                ("device_code", &device.device_code),
+                ("grant_type", GRANT_TYPE),
+            ])
+            .send()
+            .await
+            .context("Request to the token endpoint failed")?;
+
+        if response.status().is_success() {
+            let token = response
+                .json::<TokenResponse>()
+                .await
+                .context("Deserializing the token response failed")?;
+
+            return Ok(token.into());
+        }
+
+        let error = response
+            .json::<ErrorResponse>()
+            .await
+            .context("Deserializing the token error failed")?;
+
+        match error.message.as_str() {
+            "authorization_pending" => (),
+            "slow_down" => interval += Duration::from_secs(5),
+            message => bail!("Device login failed: {message}"),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+pub mod authorization_code;
+pub mod device_code;
+
+use chrono::{
+    TimeDelta,
+    Utc,
+};
+use serde::Deserialize;
+use twitch_irc::login::{
+    TokenStorage,
+    UserAccessToken,
+};
+
+use crate::{
+    credentials::ComposedCredentials,
+    token_storage::SimpleTokenStorage,
+};
+
+pub const AUTHORIZE_URL: &str = "https://id.twitch.tv/oauth2/authorize";
+pub const DEVICE_URL: &str = "https://id.twitch.tv/oauth2/device";
+pub const TOKEN_URL: &str = "https://id.twitch.tv/oauth2/token";
+pub const REDIRECT_URL: &str = "http://localhost:3000";
+
+/// The scopes the bot needs to read and write in chat
+pub const SCOPES: &str = "chat:read chat:edit";
+
+/// A successful response of Twitch's token endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+    /// Seconds until the access token expires
+    pub expires_in: i64,
+}
+
+impl From<TokenResponse> for UserAccessToken {
+    fn from(response: TokenResponse) -> Self {
+        let created_at = Utc::now();
+
+        Self {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token,
+            created_at,
+            expires_at: Some(created_at + TimeDelta::seconds(response.expires_in)),
+        }
+    }
+}
+
+pub async fn store_credentials(
+    client_id: String,
+    client_secret: String,
+    user_access_token: UserAccessToken,
+) -> anyhow::Result<()> {
+    let mut storage = SimpleTokenStorage(ComposedCredentials {
+        client_id,
+        client_secret,
+        user_access_token: user_access_token.clone(),
+    });
+
+    // goes through the token storage, so logging in writes the same file as refreshing does
+    storage.update_token(&user_access_token).await
+}
@@ -0,0 +1,420 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+        VecDeque,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use tokio::{
+    sync::mpsc,
+    time::Instant,
+};
+use twitch_irc::{
+    SecureTCPTransport,
+    TwitchIRCClient,
+    login::RefreshingLoginCredentials,
+};
+
+use crate::token_storage::SimpleTokenStorage;
+
+/// Twitch counts messages in windows of this length
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(30);
+
+/// Messages per window while sending to a channel the bot is a moderator or VIP in
+const PRIVILEGED_LIMIT: usize = 100;
+
+/// Messages per window while sending to any other channel
+const NORMAL_LIMIT: usize = 20;
+
+/// Without moderator or VIP, Twitch only accepts one message per second per channel
+const NORMAL_CHANNEL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Twitch rejects longer messages (in characters), so coalescing stops here and longer messages
+/// are truncated
+pub const MAX_MESSAGE_LENGTH: usize = 500;
+
+const COALESCE_SEPARATOR: &str = " | ";
+
+/// How many messages can wait to be picked up by the [`MessageQueueWorker`]
+const QUEUE_CAPACITY: usize = 64;
+
+/// How many messages the [`MessageQueueWorker`] keeps around to be sent. Once reached,
+/// [`MessageQueue::send`] waits as well.
+const MAX_PENDING: usize = 256;
+
+type Client = TwitchIRCClient<SecureTCPTransport, RefreshingLoginCredentials<SimpleTokenStorage>>;
+
+struct OutgoingMessage {
+    channel: String,
+    message: String,
+}
+
+/// The single way out to chat. Messages are sent in order per channel, within Twitch's rate
+/// limits.
+#[derive(Clone)]
+pub struct MessageQueue {
+    sender: mpsc::Sender<OutgoingMessage>,
+    privileged: Arc<Mutex<HashSet<String>>>,
+}
+
+impl MessageQueue {
+    /// The [`MessageQueueWorker`] does the actual sending and has to be run
+    pub fn new(client: Client) -> (Self, MessageQueueWorker) {
+        let (sender, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        let privileged = Arc::new(Mutex::new(HashSet::new()));
+
+        let worker = MessageQueueWorker {
+            client,
+            receiver,
+            privileged: privileged.clone(),
+            outbox: Outbox::default(),
+        };
+
+        (Self { sender, privileged }, worker)
+    }
+
+    /// Queues `message` for `channel`. If the queue is full, this waits until there is room.
+    pub async fn send(
+        &self,
+        channel: impl Into<String>,
+        message: impl Into<String>,
+    ) -> anyhow::Result<()> {
+        self.sender
+            .send(OutgoingMessage {
+                channel: channel.into(),
+                message: message.into(),
+            })
+            .await
+            .map_err(|_| anyhow!("the message queue stopped"))
+    }
+
+    /// Sets whether the bot is the broadcaster, a moderator or a VIP in `channel`, which raises
+    /// its rate limits
+    pub fn set_privileged(&self, channel: &str, privileged: bool) {
+        let mut channels = self.privileged.lock().unwrap();
+
+        if privileged {
+            channels.insert(channel.to_owned());
+        } else {
+            channels.remove(channel);
+        }
+    }
+}
+
+pub struct MessageQueueWorker {
+    client: Client,
+    receiver: mpsc::Receiver<OutgoingMessage>,
+    privileged: Arc<Mutex<HashSet<String>>>,
+    outbox: Outbox,
+}
+
+impl MessageQueueWorker {
+    /// Sends queued messages until every [`MessageQueue`] is dropped and nothing is left to send
+    pub async fn run(mut self) -> anyhow::Result<()> {
+        let mut open = true;
+
+        loop {
+            // once enough is pending, senders have to wait in `MessageQueue::send`
+            let receiving = open && self.outbox.len() < MAX_PENDING;
+
+            let next_ready = {
+                let privileged = self.privileged.lock().unwrap();
+                self.outbox.next_ready(Instant::now(), &privileged)
+            };
+
+            match next_ready {
+                Some((channel, ready_at)) => {
+                    tokio::select! {
+                        received = self.receiver.recv(), if receiving => match received {
+                            Some(message) => self.push(message),
+                            None => open = false,
+                        },
+                        _ = tokio::time::sleep_until(ready_at) => self.send_next(&channel).await,
+                    }
+                }
+                None if open => match self.receiver.recv().await {
+                    Some(message) => self.push(message),
+                    None => open = false,
+                },
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Queues `message` and everything else that was sent at the same time, so it can be
+    /// coalesced
+    fn push(&mut self, first: OutgoingMessage) {
+        let now = Instant::now();
+        self.outbox.push(first.channel, first.message, now);
+
+        while self.outbox.len() < MAX_PENDING
+            && let Ok(OutgoingMessage { channel, message }) = self.receiver.try_recv()
+        {
+            self.outbox.push(channel, message, now);
+        }
+    }
+
+    async fn send_next(&mut self, channel: &str) {
+        let Some(message) = self.outbox.take_next(channel, Instant::now()) else {
+            return;
+        };
+
+        if let Err(error) = self.client.say(channel.to_owned(), message).await {
+            tracing::error!(channel, ?error, "Failed to send message");
+        }
+    }
+}
+
+/// The messages waiting to be sent and the rate limits they have to keep to. Doesn't send
+/// anything itself, see [`MessageQueueWorker`].
+#[derive(Debug, Default)]
+struct Outbox {
+    /// channel -> messages, along with when they were queued
+    pending: HashMap<String, VecDeque<(Instant, String)>>,
+    last_sent: HashMap<String, Instant>,
+    /// When the messages of the current window were sent
+    sent: VecDeque<Instant>,
+}
+
+impl Outbox {
+    fn push(&mut self, channel: String, message: String, now: Instant) {
+        self.pending
+            .entry(channel)
+            .or_default()
+            .push_back((now, message));
+    }
+
+    /// How many messages are pending, over all channels
+    fn len(&self) -> usize {
+        self.pending.values().map(VecDeque::len).sum()
+    }
+
+    /// The channel that can get its next message the soonest, and when. Channels that are ready
+    /// at the same time go by their oldest pending message.
+    fn next_ready(
+        &mut self,
+        now: Instant,
+        privileged: &HashSet<String>,
+    ) -> Option<(String, Instant)> {
+        while self
+            .sent
+            .front()
+            .is_some_and(|sent_at| now.duration_since(*sent_at) >= RATE_LIMIT_WINDOW)
+        {
+            self.sent.pop_front();
+        }
+
+        self.pending
+            .iter()
+            .filter_map(|(channel, messages)| {
+                let (queued_at, _) = messages.front()?;
+                let is_privileged = privileged.contains(channel);
+
+                let limit = if is_privileged {
+                    PRIVILEGED_LIMIT
+                } else {
+                    NORMAL_LIMIT
+                };
+
+                let global_ready = match self.sent.len().checked_sub(limit) {
+                    Some(index) => self.sent[index] + RATE_LIMIT_WINDOW,
+                    None => now,
+                };
+
+                let channel_ready = match self.last_sent.get(channel) {
+                    Some(last_sent) if !is_privileged => *last_sent + NORMAL_CHANNEL_INTERVAL,
+                    _ => now,
+                };
+
+                Some((
+                    channel,
+                    global_ready.max(channel_ready).max(now),
+                    *queued_at,
+                ))
+            })
+            .min_by_key(|(_, ready_at, queued_at)| (*ready_at, *queued_at))
+            .map(|(channel, ready_at, _)| (channel.clone(), ready_at))
+    }
+
+    /// Takes the next message of `channel`, joined with as many of the following ones as fit, and
+    /// counts it as sent at `now`
+    fn take_next(&mut self, channel: &str, now: Instant) -> Option<String> {
+        let messages = self.pending.get_mut(channel)?;
+        let (_, first) = messages.pop_front()?;
+
+        let mut message = truncate(first);
+        let mut length = message.chars().count();
+
+        while let Some((_, next)) = messages.front()
+            && length + COALESCE_SEPARATOR.len() + next.chars().count() <= MAX_MESSAGE_LENGTH
+        {
+            length += COALESCE_SEPARATOR.len() + next.chars().count();
+            message.push_str(COALESCE_SEPARATOR);
+            message.push_str(next);
+            messages.pop_front();
+        }
+
+        if messages.is_empty() {
+            self.pending.remove(channel);
+        }
+
+        self.sent.push_back(now);
+        self.last_sent.insert(channel.to_owned(), now);
+
+        Some(message)
+    }
+}
+
+/// Cuts `message` down to [`MAX_MESSAGE_LENGTH`] characters, which is what Twitch counts
+fn truncate(message: String) -> String {
+    if message.chars().count() <= MAX_MESSAGE_LENGTH {
+        return message;
+    }
+
+    let mut truncated = message
+        .chars()
+        .take(MAX_MESSAGE_LENGTH - 1)
+        .collect::<String>();
+    truncated.push('…');
+
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        time::Duration,
+    };
+
+    use tokio::time::Instant;
+
+    use crate::outgoing::{
+        MAX_MESSAGE_LENGTH,
+        NORMAL_CHANNEL_INTERVAL,
+        NORMAL_LIMIT,
+        Outbox,
+        PRIVILEGED_LIMIT,
+        RATE_LIMIT_WINDOW,
+    };
+
+    /// Too long to be coalesced with another one
+    fn long_message() -> String {
+        "x".repeat(300)
+    }
+
+    #[test]
+    fn test_channel_interval() {
+        let now = Instant::now();
+        let mut outbox = Outbox::default();
+
+        outbox.push("normal".to_owned(), long_message(), now);
+        outbox.push("normal".to_owned(), long_message(), now);
+        outbox.take_next("normal", now).unwrap();
+
+        assert_eq!(
+            outbox.next_ready(now, &HashSet::new()),
+            Some(("normal".to_owned(), now + NORMAL_CHANNEL_INTERVAL))
+        );
+
+        let privileged = HashSet::from(["normal".to_owned()]);
+        assert_eq!(
+            outbox.next_ready(now, &privileged),
+            Some(("normal".to_owned(), now))
+        );
+    }
+
+    #[test]
+    fn test_window_limits() {
+        let now = Instant::now();
+        let mut outbox = Outbox::default();
+
+        for i in 0..NORMAL_LIMIT {
+            let channel = format!("channel_{i}");
+            outbox.push(channel.clone(), long_message(), now);
+            outbox.take_next(&channel, now).unwrap();
+        }
+
+        outbox.push("normal".to_owned(), long_message(), now);
+        assert_eq!(
+            outbox.next_ready(now, &HashSet::new()),
+            Some(("normal".to_owned(), now + RATE_LIMIT_WINDOW))
+        );
+
+        let privileged = HashSet::from(["normal".to_owned()]);
+        assert_eq!(
+            outbox.next_ready(now, &privileged),
+            Some(("normal".to_owned(), now))
+        );
+
+        for i in NORMAL_LIMIT..PRIVILEGED_LIMIT {
+            let channel = format!("channel_{i}");
+            outbox.push(channel.clone(), long_message(), now);
+            outbox.take_next(&channel, now).unwrap();
+        }
+
+        assert_eq!(
+            outbox.next_ready(now, &privileged),
+            Some(("normal".to_owned(), now + RATE_LIMIT_WINDOW))
+        );
+    }
+
+    #[test]
+    fn test_oldest_message_goes_first() {
+        let now = Instant::now();
+        let mut outbox = Outbox::default();
+
+        outbox.push("b".to_owned(), long_message(), now);
+        outbox.push(
+            "a".to_owned(),
+            long_message(),
+            now + Duration::from_millis(1),
+        );
+
+        let later = now + Duration::from_secs(1);
+        assert_eq!(
+            outbox.next_ready(later, &HashSet::new()),
+            Some(("b".to_owned(), later))
+        );
+    }
+
+    #[test]
+    fn test_coalescing_stops_at_the_limit() {
+        let now = Instant::now();
+        let mut outbox = Outbox::default();
+
+        // multi-byte characters count as one, like on Twitch
+        let first = "ä".repeat(200);
+        let second = "ö".repeat(MAX_MESSAGE_LENGTH - 200 - 3);
+
+        outbox.push("channel".to_owned(), first.clone(), now);
+        outbox.push("channel".to_owned(), second.clone(), now);
+        outbox.push("channel".to_owned(), "next".to_owned(), now);
+
+        assert_eq!(
+            outbox.take_next("channel", now),
+            Some(format!("{first} | {second}"))
+        );
+        assert_eq!(outbox.take_next("channel", now), Some("next".to_owned()));
+        assert_eq!(outbox.len(), 0);
+    }
+
+    #[test]
+    fn test_long_messages_are_truncated() {
+        let now = Instant::now();
+        let mut outbox = Outbox::default();
+
+        outbox.push("channel".to_owned(), "x".repeat(600), now);
+
+        let message = outbox.take_next("channel", now).unwrap();
+        assert_eq!(message.chars().count(), MAX_MESSAGE_LENGTH);
+        assert!(message.ends_with('…'));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+};
+
+use chrono::{
+    DateTime,
+    TimeDelta,
+    Utc,
+};
+use tokio::sync::Mutex;
+
+pub const SENT_NOTIFICATIONS_PATH: &str = "./sent_notifications.json";
+
+/// Events older than this are forgotten, so the file doesn't grow forever
+const RETENTION: TimeDelta = TimeDelta::days(14);
+
+/// event id -> channel -> when the notification was sent
+type Entries = BTreeMap<String, BTreeMap<String, DateTime<Utc>>>;
+
+/// Remembers which notification was sent to which channel, so restarts don't repeat them.
+///
+/// Stored in `sent_notifications.json`, next to `config.json`.
+#[derive(Debug)]
+pub struct SentNotifications {
+    path: PathBuf,
+    entries: Mutex<Entries>,
+}
+
+impl SentNotifications {
+    /// Loads the sent notifications. A missing or broken file starts out empty.
+    pub async fn load() -> Self {
+        Self::load_from(SENT_NOTIFICATIONS_PATH.into()).await
+    }
+
+    async fn load_from(path: PathBuf) -> Self {
+        let entries = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|error| {
+                tracing::warn!("Ignoring malformed {}: {error}", path.display());
+                Entries::new()
+            }),
+            Err(_) => Entries::new(),
+        };
+
+        Self {
+            path,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Records the notification for `event` as sent to `channel` and saves the file. Returns
+    /// `false` if it was already sent, in which case it must not be sent again.
+    ///
+    /// Checking and recording happen at once, so concurrent callers can't both claim an event.
+    /// Failing to save the file is logged, the claim still holds until the bot restarts.
+    pub async fn claim(&self, event: &str, channel: &str) -> bool {
+        let mut entries = self.entries.lock().await;
+
+        let channels = entries.entry(event.to_owned()).or_default();
+
+        if channels.contains_key(channel) {
+            return false;
+        }
+
+        let now = Utc::now();
+        channels.insert(channel.to_owned(), now);
+        prune(&mut entries, now);
+
+        // keep the lock while writing, so an older state can't overwrite a newer one
+        if let Err(error) = self.save(&entries).await {
+            tracing::error!(
+                event,
+                channel,
+                "Failed to save {}: {error:#}",
+                self.path.display()
+            );
+        }
+
+        true
+    }
+
+    /// Writes to a temporary file first, so a crash can't leave a half-written file behind
+    async fn save(&self, entries: &Entries) -> anyhow::Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&temp_path, serde_json::to_string_pretty(entries)?).await?;
+        tokio::fs::rename(&temp_path, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+/// Forgets everything sent longer than [`RETENTION`] before `now`
+fn prune(entries: &mut Entries, now: DateTime<Utc>) {
+    entries.retain(|_, channels| {
+        channels.retain(|_, sent_at| now - *sent_at < RETENTION);
+        !channels.is_empty()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::{
+        TimeDelta,
+        Utc,
+    };
+
+    use crate::sent_notifications::{
+        Entries,
+        RETENTION,
+        SentNotifications,
+        prune,
+    };
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "sent_notifications_{name}_{}.json",
+            std::process::id()
+        ))
+    }
+
+    #[test]
+    fn test_prune_forgets_old_events() {
+        let now = Utc::now();
+
+        let mut entries = Entries::new();
+        entries.entry("old".to_owned()).or_default().insert(
+            "channel".to_owned(),
+            now - RETENTION - TimeDelta::minutes(1),
+        );
+        entries
+            .entry("mixed".to_owned())
+            .or_default()
+            .insert("old_channel".to_owned(), now - RETENTION);
+        entries
+            .entry("mixed".to_owned())
+            .or_default()
+            .insert("new_channel".to_owned(), now - TimeDelta::days(1));
+
+        prune(&mut entries, now);
+
+        assert!(!entries.contains_key("old"));
+        assert_eq!(entries["mixed"].keys().collect::<Vec<_>>(), ["new_channel"]);
+    }
+
+    #[tokio::test]
+    async fn test_claims_survive_reloading() {
+        let path = temp_path("round_trip");
+        let _ = std::fs::remove_file(&path);
+
+        let sent = SentNotifications::load_from(path.clone()).await;
+        assert!(sent.claim("sorties:1", "channel").await);
+        assert!(!sent.claim("sorties:1", "channel").await);
+
+        let reloaded = SentNotifications::load_from(path.clone()).await;
+        assert!(!reloaded.claim("sorties:1", "channel").await);
+        assert!(reloaded.claim("sorties:1", "other_channel").await);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_file_starts_empty() {
+        let path = temp_path("corrupt");
+        std::fs::write(&path, "{ not json").unwrap();
+
+        let sent = SentNotifications::load_from(path.clone()).await;
+        assert!(sent.claim("sorties:1", "channel").await);
+
+        // the broken file got replaced by a valid one
+        let reloaded = SentNotifications::load_from(path.clone()).await;
+        assert!(!reloaded.claim("sorties:1", "channel").await);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_claims_hold_if_saving_fails() {
+        let path = temp_path("missing_directory").join("sent_notifications.json");
+
+        let sent = SentNotifications::load_from(path.clone()).await;
+        assert!(sent.claim("sorties:1", "channel").await);
+        assert!(!sent.claim("sorties:1", "channel").await);
+
+        assert!(!path.exists());
+    }
+}
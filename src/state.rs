@@ -4,6 +4,7 @@ use std::{
 };
 
 use arbitration_data::ArbitrationData;
+use tokio::sync::watch;
 use twitch_irc::{
     SecureTCPTransport,
     TwitchIRCClient,
@@ -17,33 +18,54 @@ use warframe::{
 use crate::{
     commands::{
         self,
-        config::CommandConfig,
+        config::{
+            CommandConfig,
+            CommandConfigOptions,
+        },
     },
-    config::Config,
+    config::Settings,
     credentials::ComposedCredentials,
-    listener::config::ListenerConfig,
+    listener::{
+        config::{
+            EndingListenerConfig,
+            ListenerConfig,
+            ListenerConfigOptions,
+        },
+        schedule::Scheduler,
+    },
+    outgoing::MessageQueue,
     placeholder::{
         ChannelName,
         Placeholder,
         apply_placeholders,
     },
+    sent_notifications::SentNotifications,
     token_storage::SimpleTokenStorage,
 };
 
 #[derive(Clone)]
 pub struct State {
     pub client: TwitchIRCClient<SecureTCPTransport, RefreshingLoginCredentials<SimpleTokenStorage>>,
-    pub config: Arc<Config>,
+    /// Use this over `client` to send messages, it keeps to Twitch's rate limits
+    pub messages: MessageQueue,
+    /// The current settings, updated whenever config.json is reloaded
+    pub settings: watch::Receiver<Arc<Settings>>,
     pub credentials: Arc<ComposedCredentials>,
     pub arbi_data: Arc<ArbitrationData>,
-    pub channel_name: ChannelName,
+    /// The channels the bot joined
+    pub channels: Arc<[ChannelName]>,
     pub wf: worldstate::Client,
     pub wfm: Arc<market::Client>,
+    /// Listener notifications that were already sent, see [`State::send_listener_message`]
+    pub sent: Arc<SentNotifications>,
+    /// Quiet hours and live-only notifications
+    pub scheduler: Arc<Scheduler>,
 }
 
 impl State {
     pub async fn send_command_response<P: Placeholder>(
         &self,
+        channel: &str,
         fmt: &str,
         author: &str,
         placeholders: impl IntoIterator<Item = P>,
@@ -57,35 +79,146 @@ impl State {
             iter::once(&author_placeholder as &dyn Placeholder),
         );
 
-        self.client
-            .say(self.channel_name.to_string(), final_message)
-            .await?;
+        self.messages.send(channel, final_message).await?;
 
         Ok(())
     }
 
+    /// Sends a notification to every channel that has the listener selected by `select` enabled.
+    ///
+    /// Failing to notify a channel is logged, the remaining channels are still notified.
     pub async fn send_listener_response<P: Placeholder>(
         &self,
-        fmt: &str,
+        event: &str,
+        select: impl Fn(&ListenerConfig) -> &ListenerConfigOptions,
         placeholders: impl IntoIterator<Item = P>,
+    ) {
+        let placeholders = placeholders.into_iter().collect::<Vec<_>>();
+
+        self.send_listener_message(event, |cfg| {
+            let options = select(cfg);
+
+            options
+                .enabled
+                .then(|| apply_placeholders(&options.format, &placeholders))
+        })
+        .await
+    }
+
+    /// Sends the `end_format` of the listener selected by `select` to every channel that has
+    /// the listener enabled and an `end_format` set. Channels `select` returns `None` for are
+    /// skipped.
+    pub async fn send_listener_end<P: Placeholder>(
+        &self,
+        event: &str,
+        select: impl Fn(&ListenerConfig) -> Option<&EndingListenerConfig>,
+        placeholders: impl IntoIterator<Item = P>,
+    ) {
+        let placeholders = placeholders.into_iter().collect::<Vec<_>>();
+
+        self.send_listener_message(event, |cfg| {
+            let config = select(cfg)?;
+
+            config
+                .end_format
+                .as_ref()
+                .filter(|_| config.options.enabled)
+                .map(|end_format| apply_placeholders(end_format, &placeholders))
+        })
+        .await
+    }
+
+    /// Sends the message `message` builds from a channel's config to that channel, skipping
+    /// channels it returns `None` for. `{channel_name}` is filled in afterwards.
+    ///
+    /// Use this over [`State::send_listener_response`] if the message depends on more than a
+    /// format, like a per-channel watchlist.
+    ///
+    /// `event` identifies the event the message is about, e.g. `sorties:<sortie id>`. Each event
+    /// is sent at most once per channel, even across restarts.
+    ///
+    /// Channels in their quiet hours (or offline, if they only want notifications while live)
+    /// don't get the message. It might be part of a summary later, see
+    /// [`crate::listener::config::ScheduleConfig`].
+    pub async fn send_listener_message(
+        &self,
+        event: &str,
+        message: impl Fn(&ListenerConfig) -> Option<String>,
+    ) {
+        let settings = self.settings();
+
+        for channel_name in self.channels.iter() {
+            let listener_config = settings.listener_cfg(&channel_name.0);
+
+            // one failing channel shouldn't keep the others from getting the notification
+            if let Err(error) = self
+                .send_listener_message_to(channel_name, event, listener_config, &message)
+                .await
+            {
+                tracing::error!(
+                    channel = %channel_name,
+                    event,
+                    "Failed to send a listener notification: {error:#}"
+                );
+            }
+        }
+    }
+
+    async fn send_listener_message_to(
+        &self,
+        channel_name: &ChannelName,
+        event: &str,
+        listener_config: &ListenerConfig,
+        message: impl Fn(&ListenerConfig) -> Option<String>,
     ) -> anyhow::Result<()> {
-        let intermediate_message = apply_placeholders(fmt, placeholders);
+        let Some(intermediate_message) = message(listener_config) else {
+            return Ok(());
+        };
+
+        if !self.sent.claim(event, &channel_name.0).await {
+            return Ok(());
+        }
 
-        let final_message =
-            apply_placeholders(&intermediate_message, iter::once(&self.channel_name));
+        let final_message = apply_placeholders(&intermediate_message, iter::once(channel_name));
 
-        self.client
-            .say(self.channel_name.to_string(), final_message)
-            .await?;
+        let schedule = &listener_config.schedule;
+
+        if self.scheduler.allows(&channel_name.0, schedule).await {
+            self.messages
+                .send(channel_name.to_string(), final_message)
+                .await?;
+        } else {
+            self.scheduler
+                .suppress(&channel_name.0, schedule, final_message)
+                .await;
+        }
 
         Ok(())
     }
 
-    pub fn listener_cfg(&self) -> &ListenerConfig {
-        &self.config.listener_config
+    /// A snapshot of the current settings
+    pub fn settings(&self) -> Arc<Settings> {
+        self.settings.borrow().clone()
+    }
+
+    /// Whether `enabled` returns true for the listener config of at least one channel
+    pub fn listener_enabled(&self, enabled: impl Fn(&ListenerConfig) -> bool) -> bool {
+        let settings = self.settings();
+
+        self.channels
+            .iter()
+            .any(|channel| enabled(settings.listener_cfg(&channel.0)))
     }
 
-    pub fn command_cfg(&self) -> &CommandConfig {
-        &self.config.command_config
+    /// Whether the command selected by `select` is enabled in at least one channel
+    pub fn command_enabled(
+        &self,
+        select: impl Fn(&CommandConfig) -> &CommandConfigOptions,
+    ) -> bool {
+        let settings = self.settings();
+
+        self.channels
+            .iter()
+            .any(|channel| select(settings.command_cfg(&channel.0)).enabled)
     }
 }
@@ -7,7 +7,7 @@ use crate::credentials::ComposedCredentials;
 
 pub const CREDENTIALS_PATH: &str = "./.credentials.json";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SimpleTokenStorage(pub ComposedCredentials);
 
 #[async_trait::async_trait]